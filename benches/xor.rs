@@ -0,0 +1,59 @@
+//! XOR and pad generation throughput at small, medium and large buffer
+//! sizes, to catch regressions such as an extra allocation or copy in a hot
+//! path.
+//!
+//! cargo bench --bench xor
+//! cargo bench --bench xor --features parallel
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use one_time_pad::OneTimePad;
+
+const SIZES: [(&str, usize); 3] = [("1KiB", 1 << 10), ("1MiB", 1 << 20), ("64MiB", 64 << 20)];
+
+fn xor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("xor");
+    // 64 MiB iterations take tens of milliseconds, so keep the run short.
+    group.sample_size(10);
+
+    for &(name, length) in SIZES.iter() {
+        let pad = OneTimePad::generate_random_pad(length).unwrap();
+        let data = OneTimePad::generate_random_pad(length).unwrap();
+        let mut output = vec![0u8; length];
+        group.throughput(Throughput::Bytes(length as u64));
+
+        group.bench_with_input(BenchmarkId::new("encrypt", name), &data, |b, data| {
+            b.iter(|| OneTimePad::encrypt(&pad, data).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("encrypt_into", name), &data, |b, data| {
+            b.iter(|| OneTimePad::encrypt_into(&pad, data, &mut output).unwrap())
+        });
+        group.bench_function(BenchmarkId::new("xor_in_place", name), |b| {
+            // Applying the pad twice restores the data, so the buffer can be
+            // reused across iterations.
+            b.iter(|| OneTimePad::xor_in_place(&pad, &mut output).unwrap())
+        });
+        #[cfg(feature = "parallel")]
+        group.bench_with_input(BenchmarkId::new("encrypt_parallel", name), &data, |b, data| {
+            b.iter(|| OneTimePad::encrypt_parallel(&pad, data).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    group.sample_size(10);
+
+    for &(name, length) in SIZES.iter() {
+        group.throughput(Throughput::Bytes(length as u64));
+        group.bench_function(BenchmarkId::new("generate_random_pad", name), |b| {
+            b.iter(|| OneTimePad::generate_random_pad(length).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, xor, generate);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+//! Times `encrypt` against `encrypt_parallel` over a range of buffer sizes to
+//! show where the parallel path starts to pay off on this machine.
+//!
+//! cargo run --release --features parallel --example parallel_crossover
+
+use one_time_pad::OneTimePad;
+use std::time::{Duration, Instant};
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    // Repeat small sizes so the timings are not dominated by clock noise.
+    let mut runs = 0u32;
+    let start = Instant::now();
+    while runs < 3 || start.elapsed() < Duration::from_millis(200) {
+        f();
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    println!("threads: {}", rayon::current_num_threads());
+    println!("{:>12} {:>14} {:>14} {:>8}", "bytes", "serial", "parallel", "speedup");
+
+    for shift in 10..=27 {
+        let length = 1usize << shift;
+        let pad = OneTimePad::generate_random_pad(length).unwrap();
+        let data = OneTimePad::generate_random_pad(length).unwrap();
+
+        let serial = time(|| {
+            OneTimePad::encrypt(&pad, &data).unwrap();
+        });
+        let parallel = time(|| {
+            OneTimePad::encrypt_parallel(&pad, &data).unwrap();
+        });
+
+        println!(
+            "{:>12} {:>14?} {:>14?} {:>7.2}x",
+            length,
+            serial,
+            parallel,
+            serial.as_secs_f64() / parallel.as_secs_f64()
+        );
+    }
+}
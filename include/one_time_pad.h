@@ -0,0 +1,50 @@
+#ifndef ONE_TIME_PAD_H
+#define ONE_TIME_PAD_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The call succeeded.
+#define OTP_OK 0
+
+// A pointer argument was null.
+#define OTP_ERROR_NULL_POINTER 1
+
+// The length argument was zero.
+#define OTP_ERROR_EMPTY_BUFFER 2
+
+// The random number generator failed.
+#define OTP_ERROR_RNG 3
+
+// The library panicked; this is a bug.
+#define OTP_ERROR_PANIC 99
+
+// XORs `len` bytes of `data` with `pad` into `out`.
+//
+// # Safety
+//
+// `pad` and `data` must be valid for reading and `out` valid for writing
+// `len` bytes. `out` may be the same buffer as `data` to encrypt in place,
+// but must not overlap `pad`.
+int otp_encrypt(const uint8_t *pad, const uint8_t *data, uint8_t *out, size_t len);
+
+// Reverses `otp_encrypt`. The same rules apply.
+//
+// # Safety
+//
+// See `otp_encrypt`.
+int otp_decrypt(const uint8_t *pad, const uint8_t *data, uint8_t *out, size_t len);
+
+// Fills `out` with `len` random pad bytes from the operating system.
+//
+// # Safety
+//
+// `out` must be valid for writing `len` bytes.
+int otp_generate_pad(uint8_t *out, size_t len);
+
+#endif  /* ONE_TIME_PAD_H */
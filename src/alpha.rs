@@ -0,0 +1,204 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{OneTimePad, OtpError};
+
+const LETTERS: u8 = 26;
+// The largest multiple of 26 that fits in a byte; random bytes at or above
+// this are discarded so every letter is equally likely.
+const LETTER_SAMPLE_LIMIT: u8 = 234;
+
+/// How the letter-only functions treat characters in the text that are not
+/// letters A to Z.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonLetters {
+    /// Drop them from the output.
+    Strip,
+    /// Fail with `OtpError::InvalidLetter`.
+    Reject,
+    /// Copy them to the output unchanged without using any pad.
+    PassThrough,
+}
+
+impl OneTimePad {
+    /// Classic Vernam encryption over the alphabet: each letter of the text is
+    /// added to the next pad letter mod 26 (A = 0, ..., Z = 25).
+    ///
+    /// Letters in the text and pad may be either case and the output is
+    /// uppercase. Whitespace in the pad is ignored so printed groups can be
+    /// used directly. The pad must have at least as many letters as the text.
+    ///
+    /// ```rust
+    /// use one_time_pad::{NonLetters, OneTimePad};
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let encrypted = OneTimePad::encrypt_alpha("XMCKL", "hello", NonLetters::Reject)?;
+    ///     assert_eq!(encrypted, "EQNVZ");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_alpha(pad: &str, plain_text: &str, non_letters: NonLetters) -> Result<String, OtpError> {
+        operate_alpha(pad, plain_text, non_letters, |text, pad| (text + pad) % LETTERS)
+    }
+
+    /// Reverses `encrypt_alpha` by subtracting the pad letters mod 26.
+    ///
+    /// ```rust
+    /// use one_time_pad::{NonLetters, OneTimePad};
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let decrypted = OneTimePad::decrypt_alpha("XMCKL", "EQNVZ", NonLetters::Reject)?;
+    ///     assert_eq!(decrypted, "HELLO");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_alpha(pad: &str, encrypted_text: &str, non_letters: NonLetters) -> Result<String, OtpError> {
+        operate_alpha(pad, encrypted_text, non_letters, |text, pad| {
+            (text + LETTERS - pad) % LETTERS
+        })
+    }
+
+    /// Generates `length` uniformly distributed uppercase letters for use as
+    /// a letter pad.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    ///
+    /// fn main() {
+    ///     let pad = OneTimePad::generate_alpha_pad(10).unwrap();
+    ///     println!("Letter Pad: {}", pad);
+    /// }
+    /// ```
+    pub fn generate_alpha_pad(length: usize) -> Result<String, OtpError> {
+        let mut pad = String::with_capacity(length);
+        let mut random = [0u8; 64];
+
+        while pad.len() < length {
+            getrandom::getrandom(&mut random)?;
+            for &byte in random.iter().filter(|&&byte| byte < LETTER_SAMPLE_LIMIT) {
+                if pad.len() == length {
+                    break;
+                }
+                pad.push(char::from(b'A' + byte % LETTERS));
+            }
+        }
+
+        Ok(pad)
+    }
+}
+
+fn letter_value(character: char) -> Option<u8> {
+    if character.is_ascii_alphabetic() {
+        Some(character.to_ascii_uppercase() as u8 - b'A')
+    } else {
+        None
+    }
+}
+
+fn operate_alpha<F: Fn(u8, u8) -> u8>(
+    pad: &str,
+    text: &str,
+    non_letters: NonLetters,
+    combine: F,
+) -> Result<String, OtpError> {
+    let mut pad_letters = Vec::with_capacity(pad.len());
+    for (index, character) in pad.chars().enumerate() {
+        match letter_value(character) {
+            Some(value) => pad_letters.push(value),
+            None if character.is_whitespace() => {}
+            None => return Err(OtpError::InvalidLetter { character, index }),
+        }
+    }
+
+    let needed = text.chars().filter(char::is_ascii_alphabetic).count();
+    if needed > pad_letters.len() {
+        return Err(OtpError::PadExhausted {
+            needed,
+            remaining: pad_letters.len(),
+        });
+    }
+
+    let mut pad_letters = pad_letters.into_iter();
+    let mut result = String::with_capacity(text.len());
+    for (index, character) in text.chars().enumerate() {
+        match letter_value(character) {
+            Some(value) => {
+                // Checked above: there is a pad letter for every text letter.
+                let pad_value = pad_letters.next().unwrap_or_default();
+                result.push(char::from(b'A' + combine(value, pad_value)));
+            }
+            None => match non_letters {
+                NonLetters::Strip => {}
+                NonLetters::Reject => return Err(OtpError::InvalidLetter { character, index }),
+                NonLetters::PassThrough => result.push(character),
+            },
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{NonLetters, OneTimePad, OtpError};
+
+    #[test]
+    fn known_answer() {
+        // H(7) + X(23) = 30 = E, E(4) + M(12) = Q, L(11) + C(2) = N,
+        // L(11) + K(10) = V, O(14) + L(11) = Z
+        let encrypted = OneTimePad::encrypt_alpha("XMCKL", "HELLO", NonLetters::Reject).unwrap();
+        assert_eq!(encrypted, "EQNVZ");
+
+        let decrypted = OneTimePad::decrypt_alpha("XMCKL", "EQNVZ", NonLetters::Reject).unwrap();
+        assert_eq!(decrypted, "HELLO");
+    }
+
+    #[test]
+    fn wraps_at_both_ends_of_the_alphabet() {
+        assert_eq!(OneTimePad::encrypt_alpha("A", "Z", NonLetters::Reject).unwrap(), "Z");
+        assert_eq!(OneTimePad::encrypt_alpha("B", "Z", NonLetters::Reject).unwrap(), "A");
+        assert_eq!(OneTimePad::decrypt_alpha("B", "A", NonLetters::Reject).unwrap(), "Z");
+        assert_eq!(OneTimePad::decrypt_alpha("Z", "Y", NonLetters::Reject).unwrap(), "Z");
+    }
+
+    #[test]
+    fn non_letter_policies() {
+        let pad = "XMCKL ABCDE";
+
+        let stripped = OneTimePad::encrypt_alpha(pad, "he llo!", NonLetters::Strip).unwrap();
+        assert_eq!(stripped, "EQNVZ");
+
+        let passed = OneTimePad::encrypt_alpha(pad, "he llo!", NonLetters::PassThrough).unwrap();
+        assert_eq!(passed, "EQ NVZ!");
+        let decrypted = OneTimePad::decrypt_alpha(pad, &passed, NonLetters::PassThrough).unwrap();
+        assert_eq!(decrypted, "HE LLO!");
+
+        let rejected = OneTimePad::encrypt_alpha(pad, "he llo", NonLetters::Reject);
+        assert!(matches!(rejected, Err(OtpError::InvalidLetter { character: ' ', index: 2 })));
+    }
+
+    #[test]
+    fn rejects_bad_pads() {
+        let result = OneTimePad::encrypt_alpha("XM1KL", "HELLO", NonLetters::Reject);
+        assert!(matches!(result, Err(OtpError::InvalidLetter { character: '1', index: 2 })));
+
+        let result = OneTimePad::encrypt_alpha("XMCK", "HELLO", NonLetters::Reject);
+        assert!(matches!(result, Err(OtpError::PadExhausted { needed: 5, remaining: 4 })));
+    }
+
+    #[test]
+    fn generated_pads_round_trip_and_cover_alphabet() {
+        let pad = OneTimePad::generate_alpha_pad(2600).unwrap();
+        assert_eq!(pad.len(), 2600);
+        assert!(pad.chars().all(|c| c.is_ascii_uppercase()));
+        assert!((b'A'..=b'Z').all(|letter| pad.contains(char::from(letter))));
+
+        let text = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG";
+        let encrypted = OneTimePad::encrypt_alpha(&pad, text, NonLetters::Reject).unwrap();
+        let decrypted = OneTimePad::decrypt_alpha(&pad, &encrypted, NonLetters::Reject).unwrap();
+        assert_eq!(decrypted, text);
+    }
+}
@@ -0,0 +1,121 @@
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::io::pad_exhausted;
+use crate::stream::buffer;
+use crate::{xor, OneTimePad};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+impl OneTimePad {
+    /// Async version of `encrypt_file` using `tokio::fs`, so encrypting
+    /// large files doesn't block the runtime. Returns the number of bytes
+    /// encrypted.
+    ///
+    /// Fails before writing anything if the pad file is shorter than the
+    /// input, and refuses to replace an existing `output` unless `overwrite`
+    /// is set.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let encrypted = OneTimePad::encrypt_file_async(
+    ///         Path::new("message.txt"),
+    ///         Path::new("pad.bin"),
+    ///         Path::new("message.txt.encrypted"),
+    ///         false,
+    ///     )
+    ///     .await?;
+    ///     println!("Encrypted {} bytes", encrypted);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn encrypt_file_async(input: &Path, pad: &Path, output: &Path, overwrite: bool) -> io::Result<u64> {
+        operate_file(input, pad, output, overwrite).await
+    }
+
+    /// Async version of `decrypt_file`. See `encrypt_file_async`.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let decrypted = OneTimePad::decrypt_file_async(
+    ///         Path::new("message.txt.encrypted"),
+    ///         Path::new("pad.bin"),
+    ///         Path::new("message.txt"),
+    ///         true,
+    ///     )
+    ///     .await?;
+    ///     println!("Decrypted {} bytes", decrypted);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn decrypt_file_async(input: &Path, pad: &Path, output: &Path, overwrite: bool) -> io::Result<u64> {
+        operate_file(input, pad, output, overwrite).await
+    }
+}
+
+async fn operate_file(input: &Path, pad: &Path, output: &Path, overwrite: bool) -> io::Result<u64> {
+    let input_len = fs::metadata(input).await?.len();
+    let pad_len = fs::metadata(pad).await?.len();
+    if pad_len < input_len {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "pad file ({} bytes) is shorter than the input ({} bytes)",
+                pad_len, input_len
+            ),
+        ));
+    }
+
+    let mut input_file = File::open(input).await?;
+    let mut pad_file = File::open(pad).await?;
+
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    let mut output_file = options.open(output).await?;
+
+    let mut pad_buffer = buffer(CHUNK_SIZE);
+    let mut data_buffer = buffer(CHUNK_SIZE);
+    let mut total = 0u64;
+
+    loop {
+        let read = input_file.read(&mut data_buffer).await?;
+        if read == 0 {
+            break;
+        }
+
+        // The input can grow after the length check, so the pad can still
+        // run out here.
+        pad_file.read_exact(&mut pad_buffer[..read]).await.map_err(|e| {
+            if e.kind() == ErrorKind::UnexpectedEof {
+                pad_exhausted()
+            } else {
+                e
+            }
+        })?;
+        xor(&pad_buffer[..read], &mut data_buffer[..read]);
+        output_file.write_all(&data_buffer[..read]).await?;
+
+        total += read as u64;
+    }
+
+    output_file.flush().await?;
+    Ok(total)
+}
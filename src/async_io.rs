@@ -0,0 +1,270 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::io::{pad_exhausted, WRITE_BUFFER_SIZE};
+use crate::xor;
+
+/// Async version of `PadReader`: wraps a `tokio::io::AsyncRead` and XORs
+/// everything read from it against a pad.
+///
+/// Pad bytes are only used for data the inner reader actually returns, so a
+/// read that is pending uses nothing. Reading fails with `UnexpectedEof` if
+/// the inner reader still has data once the pad has been used up.
+///
+/// ```rust
+/// use one_time_pad::{AsyncPadReader, OneTimePad};
+/// use std::error::Error;
+/// use tokio::io::AsyncReadExt;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+///     let mut encrypted_data = Vec::new();
+///     AsyncPadReader::new(&[1, 2, 3, 4, 5, 6][..], &pad)
+///         .read_to_end(&mut encrypted_data)
+///         .await?;
+///     println!("Encrypted Data: {:?}", encrypted_data);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncPadReader<'a, R> {
+    inner: R,
+    pad: &'a [u8],
+    position: usize,
+}
+
+impl<'a, R: AsyncRead + Unpin> AsyncPadReader<'a, R> {
+    pub fn new(inner: R, pad: &'a [u8]) -> Self {
+        AsyncPadReader {
+            inner,
+            pad,
+            position: 0,
+        }
+    }
+
+    /// Number of pad bytes used so far, which is also the number of bytes
+    /// read.
+    pub fn consumed(&self) -> usize {
+        self.position
+    }
+
+    /// Number of pad bytes still available for reading.
+    pub fn remaining(&self) -> usize {
+        self.pad.len() - self.position
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncPadReader<'_, R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let remaining = &this.pad[this.position..];
+        if remaining.is_empty() {
+            // The pad is used up, which is only fine if the data is too.
+            let mut probe = [0u8; 1];
+            let mut probe = ReadBuf::new(&mut probe);
+            return match Pin::new(&mut this.inner).poll_read(cx, &mut probe) {
+                Poll::Ready(Ok(())) if probe.filled().is_empty() => Poll::Ready(Ok(())),
+                Poll::Ready(Ok(())) => Poll::Ready(Err(pad_exhausted())),
+                other => other,
+            };
+        }
+
+        let limit = buf.remaining().min(remaining.len());
+        let mut limited = ReadBuf::new(buf.initialize_unfilled_to(limit));
+        match Pin::new(&mut this.inner).poll_read(cx, &mut limited) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        let read = limited.filled().len();
+
+        xor(&remaining[..read], &mut buf.initialize_unfilled_to(limit)[..read]);
+        buf.advance(read);
+        this.position += read;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Async version of `PadWriter`: wraps a `tokio::io::AsyncWrite` and XORs
+/// everything written to it against a pad before passing it on.
+///
+/// The pad is only advanced by the bytes the inner writer actually accepts,
+/// so a write that is pending because of backpressure uses nothing and the
+/// same bytes are encrypted again when it is retried. Writing fails with
+/// `UnexpectedEof` once the pad has been used up.
+///
+/// ```rust
+/// use one_time_pad::{AsyncPadWriter, OneTimePad};
+/// use std::error::Error;
+/// use tokio::io::AsyncWriteExt;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+///     let mut encrypted_data = Vec::new();
+///     AsyncPadWriter::new(&mut encrypted_data, &pad)
+///         .write_all(&[1, 2, 3, 4, 5, 6])
+///         .await?;
+///     println!("Encrypted Data: {:?}", encrypted_data);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncPadWriter<'a, W> {
+    inner: W,
+    pad: &'a [u8],
+    position: usize,
+}
+
+impl<'a, W: AsyncWrite + Unpin> AsyncPadWriter<'a, W> {
+    pub fn new(inner: W, pad: &'a [u8]) -> Self {
+        AsyncPadWriter {
+            inner,
+            pad,
+            position: 0,
+        }
+    }
+
+    /// Number of pad bytes used so far, which is also the number of bytes
+    /// the inner writer has accepted.
+    pub fn consumed(&self) -> usize {
+        self.position
+    }
+
+    /// Number of pad bytes still available for writing.
+    pub fn remaining(&self) -> usize {
+        self.pad.len() - self.position
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncPadWriter<'_, W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let remaining = &this.pad[this.position..];
+        if remaining.is_empty() {
+            return Poll::Ready(Err(pad_exhausted()));
+        }
+
+        let mut buffer = [0u8; WRITE_BUFFER_SIZE];
+        let length = buf.len().min(remaining.len()).min(buffer.len());
+        let buffer = &mut buffer[..length];
+        buffer.copy_from_slice(&buf[..length]);
+        xor(&remaining[..length], buffer);
+
+        let written = Pin::new(&mut this.inner).poll_write(cx, buffer);
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(buffer);
+        if let Poll::Ready(Ok(written)) = written {
+            this.position += written;
+        }
+
+        written
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{AsyncPadReader, AsyncPadWriter, OneTimePad};
+    use std::io::ErrorKind;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+    use tokio::io::{duplex, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+    #[tokio::test]
+    async fn round_trips_through_a_tiny_duplex() {
+        let plain_text: Vec<u8> = (0..1000u32).map(|i| (i % 253) as u8).collect();
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+
+        for &capacity in [1, 3, 64].iter() {
+            let (client, server) = duplex(capacity);
+            let mut writer = AsyncPadWriter::new(client, &pad);
+            let mut reader = AsyncPadReader::new(server, &pad);
+
+            let write = async {
+                for chunk in plain_text.chunks(7) {
+                    writer.write_all(chunk).await.unwrap();
+                }
+                writer.shutdown().await.unwrap();
+            };
+            let mut decrypted_data = Vec::new();
+            let read = reader.read_to_end(&mut decrypted_data);
+            let ((), read) = tokio::join!(write, read);
+
+            read.unwrap();
+            assert_eq!(decrypted_data, plain_text, "capacity {}", capacity);
+            assert_eq!((writer.consumed(), reader.consumed()), (1000, 1000));
+        }
+    }
+
+    #[test]
+    fn pending_calls_do_not_use_the_pad() {
+        let pad = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66];
+        let mut cx = Context::from_waker(Waker::noop());
+        let (client, mut server) = duplex(4);
+        let mut writer = AsyncPadWriter::new(client, &pad);
+
+        let first = Pin::new(&mut writer).poll_write(&mut cx, &[0; 6]);
+        assert!(matches!(first, Poll::Ready(Ok(4))));
+        assert!(Pin::new(&mut writer).poll_write(&mut cx, &[0; 2]).is_pending());
+        assert_eq!(writer.consumed(), 4);
+
+        let mut received = [0u8; 4];
+        let mut received_buf = ReadBuf::new(&mut received);
+        assert!(Pin::new(&mut server).poll_read(&mut cx, &mut received_buf).is_ready());
+        assert_eq!(received, [0x11, 0x22, 0x33, 0x44]);
+        assert!(matches!(Pin::new(&mut writer).poll_write(&mut cx, &[0; 2]), Poll::Ready(Ok(2))));
+        assert_eq!(writer.consumed(), 6);
+
+        let (_client, server) = duplex(4);
+        let mut reader = AsyncPadReader::new(server, &pad);
+        let mut buffer = [0u8; 4];
+        assert!(Pin::new(&mut reader).poll_read(&mut cx, &mut ReadBuf::new(&mut buffer)).is_pending());
+        assert_eq!(reader.consumed(), 0);
+    }
+
+    #[tokio::test]
+    async fn errors_when_pad_runs_out() {
+        let mut reader = AsyncPadReader::new(&[0u8; 10][..], &[1; 8]);
+        let error = reader.read_to_end(&mut Vec::new()).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+        let mut encrypted_data = Vec::new();
+        let mut writer = AsyncPadWriter::new(&mut encrypted_data, &[1, 2, 3]);
+        let error = writer.write_all(&[0, 0, 0, 0]).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(encrypted_data, vec![1, 2, 3]);
+
+        let mut short_data = Vec::new();
+        AsyncPadReader::new(&[0, 0][..], &[5, 6, 7]).read_to_end(&mut short_data).await.unwrap();
+        assert_eq!(short_data, vec![5, 6]);
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::{OtpError, Pad, PadState};
+
+/// Hands out successive, non-overlapping segments of one or more pads, so
+/// pad material can't be reused by mistake.
+///
+/// Each pad has an id. A book made from a single pad calls it `"0"`, and pads
+/// loaded from files are named after the file.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PadBook};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let mut book = PadBook::new(OneTimePad::generate_random_pad(12).unwrap());
+///     for message in [[1, 2, 3, 4, 5, 6], [6, 5, 4, 3, 2, 1]].iter() {
+///         let segment = book.next_segment(message.len())?;
+///         let encrypted_data = OneTimePad::encrypt(segment, message)?;
+///         println!("Encrypted Data: {:?}", encrypted_data);
+///     }
+///     assert!(book.next_segment(1).is_err());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct PadBook {
+    pads: Vec<(String, PadState)>,
+}
+
+/// A fresh region of one of a `PadBook`'s pads, owned so several segments
+/// can be held at once. Send `pad_id` and `offset` with the ciphertext so the
+/// receiver can find the same region of their copy.
+#[derive(Debug)]
+pub struct PadSegment {
+    pub pad_id: String,
+    pub offset: usize,
+    pub pad: Pad,
+}
+
+impl PadBook {
+    /// Creates a book holding the single pad `pad`, with id `"0"`.
+    pub fn new(pad: Pad) -> Self {
+        PadBook {
+            pads: vec![(String::from("0"), PadState::new(pad))],
+        }
+    }
+
+    /// Loads the whole pad file at `path`, resuming after the bytes its
+    /// journal records as used (see `PadState::open`). The pad's id is the
+    /// file name.
+    #[cfg(feature = "std")]
+    pub fn open(path: &Path) -> Result<Self, OtpError> {
+        let mut book = PadBook::default();
+        book.add_file(path)?;
+        Ok(book)
+    }
+
+    /// Adds `pad` under `id`. Fails with `DuplicatePadId` if the book already
+    /// has a pad with that id.
+    pub fn add_pad(&mut self, id: &str, pad: Pad) -> Result<(), OtpError> {
+        self.add_state(id, PadState::new(pad))
+    }
+
+    /// Adds the whole pad file at `path`, using the file name as its id.
+    /// Like `open`, usage is journaled next to the file.
+    #[cfg(feature = "std")]
+    pub fn add_file(&mut self, path: &Path) -> Result<(), OtpError> {
+        let id = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.add_state(&id, PadState::open(path)?)
+    }
+
+    fn add_state(&mut self, id: &str, state: PadState) -> Result<(), OtpError> {
+        if self.pads.iter().any(|(existing, _)| existing == id) {
+            return Err(OtpError::DuplicatePadId { id: String::from(id) });
+        }
+        self.pads.push((String::from(id), state));
+        Ok(())
+    }
+
+    /// Returns the next `length` unused bytes of the first pad that has that
+    /// many left. Fails with `PadExhausted`, without using anything, if no
+    /// pad does.
+    pub fn next_segment(&mut self, length: usize) -> Result<&[u8], OtpError> {
+        let index = self.find_pad(length)?;
+        self.pads[index].1.next_region(length)
+    }
+
+    /// Like `next_segment`, but returns an owned copy of the region along
+    /// with the pad id and offset it came from. Fails with `EmptyBuffer` if
+    /// `length` is zero.
+    pub fn allocate(&mut self, length: usize) -> Result<PadSegment, OtpError> {
+        if length == 0 {
+            return Err(OtpError::EmptyBuffer);
+        }
+
+        let index = self.find_pad(length)?;
+        let (id, state) = &mut self.pads[index];
+        let offset = state.consumed();
+        let region = state.next_region(length)?;
+
+        Ok(PadSegment {
+            pad_id: id.clone(),
+            offset,
+            pad: Pad::from(region.to_vec()),
+        })
+    }
+
+    /// Number of bytes of pad `id` handed out so far, or `None` if the book
+    /// has no such pad.
+    pub fn consumed(&self, id: &str) -> Option<usize> {
+        self.pads
+            .iter()
+            .find(|(existing, _)| existing == id)
+            .map(|(_, state)| state.consumed())
+    }
+
+    /// Number of pad bytes not yet handed out, across all pads.
+    pub fn remaining(&self) -> usize {
+        self.pads.iter().map(|(_, state)| state.remaining()).sum()
+    }
+
+    fn find_pad(&self, length: usize) -> Result<usize, OtpError> {
+        self.pads
+            .iter()
+            .position(|(_, state)| state.remaining() >= length)
+            .ok_or(OtpError::PadExhausted {
+                needed: length,
+                remaining: self.pads.iter().map(|(_, state)| state.remaining()).max().unwrap_or(0),
+            })
+    }
+}
+
+impl From<Vec<u8>> for PadBook {
+    fn from(pad: Vec<u8>) -> Self {
+        PadBook::new(Pad::from(pad))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OtpError, Pad, PadBook};
+
+    #[test]
+    fn segments_are_successive_and_disjoint() {
+        let mut book = PadBook::from((0..10).collect::<Vec<u8>>());
+
+        assert_eq!(book.next_segment(3).unwrap(), &[0, 1, 2]);
+        assert_eq!(book.next_segment(4).unwrap(), &[3, 4, 5, 6]);
+        assert_eq!((book.consumed("0"), book.remaining()), (Some(7), 3));
+    }
+
+    #[test]
+    fn exhaustion_does_not_consume_the_rest() {
+        let mut book = PadBook::from(vec![1, 2, 3, 4]);
+        book.next_segment(3).unwrap();
+
+        let result = book.next_segment(2);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::PadExhausted { needed: 2, remaining: 1 })
+        ));
+        assert_eq!(book.next_segment(1).unwrap(), &[4]);
+        assert_eq!(book.remaining(), 0);
+    }
+
+    #[test]
+    fn allocations_across_pads_are_disjoint_and_in_bounds() {
+        let sizes = [("a", 100), ("b", 37), ("c", 250)];
+        let mut book = PadBook::default();
+        for &(id, size) in sizes.iter() {
+            book.add_pad(id, Pad::from(vec![0; size])).unwrap();
+        }
+
+        let mut segments = Vec::new();
+        let mut length = 1;
+        while book.remaining() > 0 {
+            match book.allocate(length) {
+                Ok(segment) => segments.push(segment),
+                Err(OtpError::PadExhausted { .. }) => length = 0,
+                Err(e) => panic!("{}", e),
+            }
+            length = length % 13 + 1;
+        }
+
+        let total: usize = segments.iter().map(|segment| segment.pad.len()).sum();
+        assert_eq!(total, 387);
+        for &(id, size) in sizes.iter() {
+            let mut ranges: Vec<(usize, usize)> = segments
+                .iter()
+                .filter(|segment| segment.pad_id == id)
+                .map(|segment| (segment.offset, segment.offset + segment.pad.len()))
+                .collect();
+            ranges.sort();
+            assert!(ranges.windows(2).all(|pair| pair[0].1 <= pair[1].0), "{} overlaps", id);
+            assert!(ranges.iter().all(|&(_, end)| end <= size));
+            assert_eq!(book.consumed(id), Some(size));
+        }
+        assert!(matches!(book.allocate(1), Err(OtpError::PadExhausted { remaining: 0, .. })));
+    }
+
+    #[test]
+    fn allocate_prefers_a_pad_with_room() {
+        let mut book = PadBook::default();
+        book.add_pad("small", Pad::from(vec![1; 4])).unwrap();
+        book.add_pad("large", Pad::from(vec![2; 16])).unwrap();
+
+        let segment = book.allocate(8).unwrap();
+        assert_eq!((segment.pad_id.as_str(), segment.offset), ("large", 0));
+        assert_eq!(segment.pad.as_slice(), &[2; 8]);
+
+        let segment = book.allocate(3).unwrap();
+        assert_eq!((segment.pad_id.as_str(), segment.offset), ("small", 0));
+
+        let result = book.allocate(9);
+        assert!(matches!(result, Err(OtpError::PadExhausted { needed: 9, remaining: 8 })));
+        assert!(matches!(
+            book.add_pad("small", Pad::from(vec![0; 1])),
+            Err(OtpError::DuplicatePadId { .. })
+        ));
+        assert!(matches!(book.allocate(0), Err(OtpError::EmptyBuffer)));
+    }
+}
@@ -0,0 +1,267 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::OneTimePad;
+
+const MAGIC: &[u8; 4] = b"OTPC";
+const VERSION: u8 = 1;
+
+/// Metadata stored in front of the ciphertext in a container, telling the
+/// receiver which pad and which region of it to decrypt with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerHeader {
+    /// Identifies the pad, e.g. a file name or serial number. At most 65535
+    /// bytes of UTF-8.
+    pub pad_id: String,
+    /// Offset into the pad of the first byte used for this ciphertext.
+    pub pad_offset: u64,
+}
+
+/// Problems reading or writing an encrypted container.
+#[derive(Debug)]
+pub enum FormatError {
+    /// The data does not start with the container magic bytes.
+    BadMagic,
+    /// The container was written by an unknown version of the format.
+    UnsupportedVersion { version: u8 },
+    /// The data ends inside the header.
+    TruncatedHeader,
+    /// The data ends before the `expected` payload bytes.
+    TruncatedPayload { expected: u64, found: u64 },
+    /// The pad identifier is not valid UTF-8.
+    InvalidPadId,
+    /// The pad identifier is too long to be stored.
+    PadIdTooLong { length: usize },
+    /// Reading or writing the container failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::BadMagic => write!(f, "not an encrypted container (bad magic bytes)"),
+            FormatError::UnsupportedVersion { version } => {
+                write!(f, "unsupported container version {}", version)
+            }
+            FormatError::TruncatedHeader => write!(f, "container ends inside its header"),
+            FormatError::TruncatedPayload { expected, found } => write!(
+                f,
+                "container payload has {} bytes but the header says {}",
+                found, expected
+            ),
+            FormatError::InvalidPadId => write!(f, "pad identifier is not valid UTF-8"),
+            FormatError::PadIdTooLong { length } => {
+                write!(f, "pad identifier is {} bytes, the limit is {}", length, u16::MAX)
+            }
+            FormatError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FormatError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FormatError {
+    fn from(e: io::Error) -> Self {
+        FormatError::Io(e)
+    }
+}
+
+impl OneTimePad {
+    /// Writes `ciphertext` framed with a header so it can be recognised and
+    /// matched back to its pad later. All integers are little-endian:
+    ///
+    /// ```text
+    /// magic "OTPC" | version (u8) | pad id length (u16) | pad id (UTF-8)
+    /// | pad offset (u64) | payload length (u64) | payload
+    /// ```
+    ///
+    /// ```rust
+    /// use one_time_pad::{ContainerHeader, OneTimePad};
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     let header = ContainerHeader { pad_id: "pad-0001".to_string(), pad_offset: 0 };
+    ///
+    ///     let mut container = Vec::new();
+    ///     OneTimePad::write_container(&mut container, &header, &encrypted_data)?;
+    ///     let (loaded_header, loaded_data) = OneTimePad::read_container(&container[..])?;
+    ///     assert_eq!((loaded_header, loaded_data), (header, encrypted_data));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_container<W: Write>(
+        mut writer: W,
+        header: &ContainerHeader,
+        ciphertext: &[u8],
+    ) -> Result<(), FormatError> {
+        let pad_id = header.pad_id.as_bytes();
+        let pad_id_length = u16::try_from(pad_id.len()).map_err(|_| FormatError::PadIdTooLong {
+            length: pad_id.len(),
+        })?;
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&pad_id_length.to_le_bytes())?;
+        writer.write_all(pad_id)?;
+        writer.write_all(&header.pad_offset.to_le_bytes())?;
+        writer.write_all(&(ciphertext.len() as u64).to_le_bytes())?;
+        writer.write_all(ciphertext)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a container written by `write_container`, returning its header
+    /// and ciphertext. Anything after the payload is left unread.
+    pub fn read_container<R: Read>(mut reader: R) -> Result<(ContainerHeader, Vec<u8>), FormatError> {
+        let mut magic = [0u8; 4];
+        read_header(&mut reader, &mut magic)?;
+        if &magic != MAGIC {
+            return Err(FormatError::BadMagic);
+        }
+
+        let mut version = [0u8; 1];
+        read_header(&mut reader, &mut version)?;
+        if version[0] != VERSION {
+            return Err(FormatError::UnsupportedVersion { version: version[0] });
+        }
+
+        let mut pad_id_length = [0u8; 2];
+        read_header(&mut reader, &mut pad_id_length)?;
+        let mut pad_id = vec![0u8; usize::from(u16::from_le_bytes(pad_id_length))];
+        read_header(&mut reader, &mut pad_id)?;
+        let pad_id = String::from_utf8(pad_id).map_err(|_| FormatError::InvalidPadId)?;
+
+        let mut pad_offset = [0u8; 8];
+        read_header(&mut reader, &mut pad_offset)?;
+        let mut payload_length = [0u8; 8];
+        read_header(&mut reader, &mut payload_length)?;
+        let expected = u64::from_le_bytes(payload_length);
+
+        // Read through `take` rather than allocating `expected` bytes up
+        // front, so a corrupt length can't trigger a huge allocation.
+        let mut ciphertext = Vec::new();
+        reader.take(expected).read_to_end(&mut ciphertext)?;
+        let found = ciphertext.len() as u64;
+        if found != expected {
+            return Err(FormatError::TruncatedPayload { expected, found });
+        }
+
+        let header = ContainerHeader {
+            pad_id,
+            pad_offset: u64::from_le_bytes(pad_offset),
+        };
+        Ok((header, ciphertext))
+    }
+}
+
+fn read_header<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<(), FormatError> {
+    reader.read_exact(buffer).map_err(|e| match e.kind() {
+        ErrorKind::UnexpectedEof => FormatError::TruncatedHeader,
+        _ => FormatError::Io(e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{ContainerHeader, FormatError, OneTimePad};
+
+    fn container() -> Vec<u8> {
+        let header = ContainerHeader {
+            pad_id: "pad-7".to_string(),
+            pad_offset: 0x0102,
+        };
+        let mut container = Vec::new();
+        OneTimePad::write_container(&mut container, &header, &[0xaa, 0xbb, 0xcc]).unwrap();
+        container
+    }
+
+    #[test]
+    fn known_layout() {
+        let expected = [
+            &b"OTPC"[..],
+            &[1],
+            &[5, 0],
+            b"pad-7",
+            &[0x02, 0x01, 0, 0, 0, 0, 0, 0],
+            &[3, 0, 0, 0, 0, 0, 0, 0],
+            &[0xaa, 0xbb, 0xcc],
+        ]
+        .concat();
+
+        assert_eq!(container(), expected);
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut data = container();
+        data[0] = b'X';
+
+        let result = OneTimePad::read_container(&data[..]);
+
+        assert!(matches!(result, Err(FormatError::BadMagic)));
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let mut data = container();
+        data[4] = 2;
+
+        let result = OneTimePad::read_container(&data[..]);
+
+        assert!(matches!(result, Err(FormatError::UnsupportedVersion { version: 2 })));
+    }
+
+    #[test]
+    fn rejects_truncated_header() {
+        let data = container();
+
+        // Every cut inside the header: magic, version, id length, id, offset
+        // and payload length.
+        for length in 0..28 {
+            let result = OneTimePad::read_container(&data[..length]);
+            assert!(matches!(result, Err(FormatError::TruncatedHeader)), "length {}", length);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_payload() {
+        let data = container();
+        let result = OneTimePad::read_container(&data[..data.len() - 1]);
+        assert!(matches!(
+            result,
+            Err(FormatError::TruncatedPayload { expected: 3, found: 2 })
+        ));
+
+        // A corrupt length far beyond the data is reported, not allocated.
+        let mut data = container();
+        data[20..28].copy_from_slice(&u64::MAX.to_le_bytes());
+        let result = OneTimePad::read_container(&data[..]);
+        assert!(matches!(result, Err(FormatError::TruncatedPayload { found: 3, .. })));
+    }
+
+    #[test]
+    fn rejects_invalid_pad_ids() {
+        let mut data = container();
+        data[7] = 0xff;
+        let result = OneTimePad::read_container(&data[..]);
+        assert!(matches!(result, Err(FormatError::InvalidPadId)));
+
+        let header = ContainerHeader {
+            pad_id: "x".repeat(70_000),
+            pad_offset: 0,
+        };
+        let result = OneTimePad::write_container(Vec::new(), &header, &[1]);
+        assert!(matches!(result, Err(FormatError::PadIdTooLong { length: 70_000 })));
+    }
+}
@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::wipe::wipe;
+use crate::{error_check, xor, OtpError};
+
+/// Encrypts into a scratch buffer it owns and reuses, for hot loops over
+/// many small messages where allocating a `Vec` per call shows up in
+/// profiles. The buffer only grows when a message is longer than any before.
+///
+/// Each result borrows the context, so it must be copied or dropped before
+/// the next call. The buffer is wiped when the context is dropped.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, OtpContext};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let mut context = OtpContext::new();
+///     for message in [&b"first"[..], b"second", b"3rd"].iter() {
+///         let pad = OneTimePad::generate_random_pad(message.len())?;
+///         let encrypted_data = context.encrypt(&pad, message)?;
+///         assert_eq!(encrypted_data, &OneTimePad::encrypt(&pad, message)?[..]);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct OtpContext {
+    buffer: Vec<u8>,
+}
+
+impl OtpContext {
+    pub fn new() -> Self {
+        OtpContext { buffer: Vec::new() }
+    }
+
+    /// Creates a context that can handle messages of up to `capacity` bytes
+    /// without allocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        OtpContext {
+            buffer: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Number of bytes the scratch buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Same as `OneTimePad::encrypt`, but the result lives in the context.
+    pub fn encrypt<'a>(&'a mut self, pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<&'a [u8], OtpError> {
+        self.operate(pad_buffer, plain_text_buffer)
+    }
+
+    /// Same as `OneTimePad::decrypt`, but the result lives in the context.
+    pub fn decrypt<'a>(&'a mut self, pad_buffer: &[u8], encrypted_data_buffer: &[u8]) -> Result<&'a [u8], OtpError> {
+        self.operate(pad_buffer, encrypted_data_buffer)
+    }
+
+    fn operate<'a>(&'a mut self, pad_buffer: &[u8], data_buffer: &[u8]) -> Result<&'a [u8], OtpError> {
+        error_check(pad_buffer, data_buffer)?;
+
+        self.buffer.clear();
+        self.buffer.extend_from_slice(data_buffer);
+        xor(pad_buffer, &mut self.buffer);
+
+        Ok(&self.buffer)
+    }
+}
+
+impl Drop for OtpContext {
+    fn drop(&mut self) {
+        // Earlier, longer messages may still sit beyond the current length.
+        let capacity = self.buffer.capacity();
+        self.buffer.resize(capacity, 0);
+        wipe(&mut self.buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, OtpContext, OtpError};
+
+    #[test]
+    fn interleaved_sizes_match_stateless_encrypt() {
+        let mut context = OtpContext::new();
+
+        for &length in [4096, 3, 4096, 1, 10_000, 17].iter() {
+            let pad = OneTimePad::generate_random_pad(length).unwrap();
+            let plain_text = OneTimePad::generate_random_pad(length).unwrap();
+
+            let expected = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+            let encrypted_data = context.encrypt(&pad, &plain_text).unwrap().to_vec();
+            assert_eq!(encrypted_data, expected);
+            assert_eq!(context.decrypt(&pad, &encrypted_data).unwrap(), plain_text.as_slice());
+        }
+    }
+
+    #[test]
+    fn buffer_only_grows_for_longer_messages() {
+        let mut context = OtpContext::with_capacity(64);
+        let capacity = context.capacity();
+
+        context.encrypt(&[1; 64], &[2; 64]).unwrap();
+        context.encrypt(&[1; 8], &[2; 8]).unwrap();
+        assert_eq!(context.capacity(), capacity);
+
+        context.encrypt(&[1; 1000], &[2; 1000]).unwrap();
+        assert!(context.capacity() >= 1000);
+    }
+
+    #[test]
+    fn errors_match_stateless_encrypt() {
+        let mut context = OtpContext::new();
+
+        assert!(matches!(
+            context.encrypt(&[1, 2, 3], &[1, 2]),
+            Err(OtpError::LengthMismatch { pad_len: 3, data_len: 2 })
+        ));
+        assert!(matches!(context.encrypt(&[], &[]), Err(OtpError::EmptyBuffer)));
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{OneTimePad, OtpError};
+
+const DIGITS: u8 = 10;
+// The largest multiple of 10 that fits in a byte; random bytes at or above
+// this are discarded so every digit is equally likely.
+const DIGIT_SAMPLE_LIMIT: u8 = 250;
+
+impl OneTimePad {
+    /// Numeric one-time pad encryption: each digit of the text is added to the
+    /// next pad digit mod 10 without carrying, so the output has exactly as
+    /// many digits as the input.
+    ///
+    /// The text must contain only ASCII digits. Whitespace in the pad is
+    /// ignored so printed groups can be used directly, and the pad must have
+    /// at least as many digits as the text.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let encrypted = OneTimePad::encrypt_digits("58964", "73251")?;
+    ///     assert_eq!(encrypted, "21115");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_digits(pad: &str, plain_text: &str) -> Result<String, OtpError> {
+        operate_digits(pad, plain_text, |text, pad| (text + pad) % DIGITS)
+    }
+
+    /// Reverses `encrypt_digits` by subtracting the pad digits mod 10 without
+    /// borrowing.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let decrypted = OneTimePad::decrypt_digits("58964", "21115")?;
+    ///     assert_eq!(decrypted, "73251");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_digits(pad: &str, encrypted_text: &str) -> Result<String, OtpError> {
+        operate_digits(pad, encrypted_text, |text, pad| (text + DIGITS - pad) % DIGITS)
+    }
+
+    /// Generates `length` uniformly distributed ASCII digits for use as a
+    /// numeric pad.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    ///
+    /// fn main() {
+    ///     let pad = OneTimePad::generate_digit_pad(10).unwrap();
+    ///     println!("Digit Pad: {}", pad);
+    /// }
+    /// ```
+    pub fn generate_digit_pad(length: usize) -> Result<String, OtpError> {
+        let mut pad = String::with_capacity(length);
+        let mut random = [0u8; 64];
+
+        while pad.len() < length {
+            getrandom::getrandom(&mut random)?;
+            for &byte in random.iter().filter(|&&byte| byte < DIGIT_SAMPLE_LIMIT) {
+                if pad.len() == length {
+                    break;
+                }
+                pad.push(char::from(b'0' + byte % DIGITS));
+            }
+        }
+
+        Ok(pad)
+    }
+}
+
+fn digit_value(character: char) -> Option<u8> {
+    character.to_digit(10).map(|digit| digit as u8)
+}
+
+fn operate_digits<F: Fn(u8, u8) -> u8>(pad: &str, text: &str, combine: F) -> Result<String, OtpError> {
+    let mut pad_digits = Vec::with_capacity(pad.len());
+    for (index, character) in pad.chars().enumerate() {
+        match digit_value(character) {
+            Some(value) => pad_digits.push(value),
+            None if character.is_whitespace() => {}
+            None => return Err(OtpError::InvalidDigit { character, index }),
+        }
+    }
+
+    let mut text_digits = Vec::with_capacity(text.len());
+    for (index, character) in text.chars().enumerate() {
+        text_digits.push(digit_value(character).ok_or(OtpError::InvalidDigit { character, index })?);
+    }
+
+    if text_digits.len() > pad_digits.len() {
+        return Err(OtpError::PadExhausted {
+            needed: text_digits.len(),
+            remaining: pad_digits.len(),
+        });
+    }
+
+    Ok(text_digits
+        .iter()
+        .zip(&pad_digits)
+        .map(|(&text, &pad)| char::from(b'0' + combine(text, pad)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, OtpError};
+
+    #[test]
+    fn known_answer() {
+        // 7+5 = 2, 3+8 = 1, 2+9 = 1, 5+6 = 1, 1+4 = 5 (no carries)
+        assert_eq!(OneTimePad::encrypt_digits("58964", "73251").unwrap(), "21115");
+        assert_eq!(OneTimePad::decrypt_digits("58964", "21115").unwrap(), "73251");
+
+        assert_eq!(
+            OneTimePad::encrypt_digits("98765 43210", "1234567890").unwrap(),
+            "0000000000"
+        );
+    }
+
+    #[test]
+    fn wraps_without_carry_or_borrow() {
+        assert_eq!(OneTimePad::encrypt_digits("11", "99").unwrap(), "00");
+        assert_eq!(OneTimePad::decrypt_digits("11", "00").unwrap(), "99");
+        assert_eq!(OneTimePad::encrypt_digits("0", "9").unwrap(), "9");
+    }
+
+    #[test]
+    fn rejects_non_digits() {
+        let result = OneTimePad::encrypt_digits("12345", "12 45");
+        assert!(matches!(result, Err(OtpError::InvalidDigit { character: ' ', index: 2 })));
+
+        let result = OneTimePad::encrypt_digits("12a45", "12345");
+        assert!(matches!(result, Err(OtpError::InvalidDigit { character: 'a', index: 2 })));
+
+        let result = OneTimePad::encrypt_digits("1234", "12345");
+        assert!(matches!(result, Err(OtpError::PadExhausted { needed: 5, remaining: 4 })));
+
+        let result = OneTimePad::encrypt_digits("12345", "١٢");
+        assert!(matches!(result, Err(OtpError::InvalidDigit { index: 0, .. })));
+    }
+
+    #[test]
+    fn long_random_round_trip() {
+        let pad = OneTimePad::generate_digit_pad(10_000).unwrap();
+        let text = OneTimePad::generate_digit_pad(10_000).unwrap();
+        assert!(pad.chars().all(|c| c.is_ascii_digit()));
+        assert!(('0'..='9').all(|digit| pad.contains(digit)));
+
+        let encrypted = OneTimePad::encrypt_digits(&pad, &text).unwrap();
+        assert_eq!(encrypted.len(), text.len());
+        assert_ne!(encrypted, text);
+
+        assert_eq!(OneTimePad::decrypt_digits(&pad, &encrypted).unwrap(), text);
+    }
+}
@@ -0,0 +1,104 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::{OneTimePad, OtpError};
+
+impl OneTimePad {
+    /// Encrypts and encodes the result as standard, padded base64 so it can
+    /// be embedded in JSON, email and other text formats.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_to_base64(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_to_base64(pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<String, OtpError> {
+        OneTimePad::encrypt(pad_buffer, plain_text_buffer).map(|encrypted_data| STANDARD.encode(encrypted_data))
+    }
+
+    /// Decodes standard, padded base64 and decrypts the result.
+    ///
+    /// Invalid characters, bad padding and embedded whitespace or newlines are
+    /// all reported as `InvalidBase64`; strip line breaks before decoding
+    /// wrapped text. Lengths are compared after decoding, so a mismatch is
+    /// reported in bytes rather than base64 characters.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let decrypted_data = OneTimePad::decrypt_from_base64(&pad, "AQIDBAUG")?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_from_base64(pad_buffer: &[u8], encrypted_data: &str) -> Result<Vec<u8>, OtpError> {
+        let encrypted_data = STANDARD.decode(encrypted_data).map_err(OtpError::InvalidBase64)?;
+        OneTimePad::decrypt(pad_buffer, &encrypted_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, OtpError};
+
+    #[test]
+    fn base64_round_trip() {
+        let pad = [7, 6, 5, 4, 3, 2];
+
+        let encrypted_data = OneTimePad::encrypt_to_base64(&pad, &[1, 2, 3, 4, 5, 6]).unwrap();
+
+        assert_eq!(encrypted_data, "BgQGAAYE");
+        assert_eq!(
+            OneTimePad::decrypt_from_base64(&pad, &encrypted_data).unwrap(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn invalid_base64_is_an_error() {
+        let pad = [1, 2, 3];
+
+        for invalid in &["AQ*D", "AQI", "AQ==AQ==", "AQID\n", "AQ ID", " AQID"] {
+            let result = OneTimePad::decrypt_from_base64(&pad, invalid);
+
+            assert!(matches!(result, Err(OtpError::InvalidBase64(_))), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn length_mismatch_is_reported_in_bytes() {
+        let result = OneTimePad::decrypt_from_base64(&[1, 2, 3], "AQIDBA==");
+
+        assert!(matches!(
+            result,
+            Err(OtpError::LengthMismatch { pad_len: 3, data_len: 4 })
+        ));
+    }
+
+    #[test]
+    fn round_trip_through_json() {
+        let pad = OneTimePad::generate_random_pad(32).unwrap();
+        let plain_text = b"attack at dawn, bring snacks....";
+
+        let message = serde_json::json!({
+            "ciphertext": OneTimePad::encrypt_to_base64(&pad, plain_text).unwrap(),
+        });
+        let parsed: serde_json::Value = serde_json::from_str(&message.to_string()).unwrap();
+        let ciphertext = parsed["ciphertext"].as_str().unwrap();
+
+        assert_eq!(OneTimePad::decrypt_from_base64(&pad, ciphertext).unwrap(), plain_text);
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::vec::Vec;
+
+use crate::wipe::wipe;
+use crate::{error_check, pad_segment, xor, OtpError};
+
+/// Configures a single encryption in one place instead of picking between
+/// `encrypt`, `encrypt_with_offset` and `encrypt_and_zeroize`. With no
+/// options set it behaves exactly like `encrypt`.
+///
+/// - `offset(n)` skips the first `n` pad bytes.
+/// - `allow_partial(true)` lets the pad, after the offset, be longer than
+///   the data; only the first `data.len()` bytes are used, like
+///   `encrypt_with_offset`. Without it, the pad after the offset must be
+///   exactly as long as the data.
+/// - `zeroize_pad(true)` overwrites the pad bytes that were used with zeros
+///   once encryption succeeds, so they can't be used again. Nothing is
+///   wiped if encryption fails, since nothing was used.
+///
+/// The pad is borrowed mutably so that `zeroize_pad` can wipe it.
+///
+/// ```rust
+/// use one_time_pad::{Encryptor, OneTimePad};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let mut pad = vec![7, 6, 5, 4, 3, 2, 1, 0];
+///     let encrypted_data = Encryptor::new(&mut pad)
+///         .offset(2)
+///         .allow_partial(true)
+///         .zeroize_pad(true)
+///         .encrypt(&[1, 2, 3])?;
+///     assert_eq!(encrypted_data, OneTimePad::encrypt([5, 4, 3], [1, 2, 3])?);
+///     assert_eq!(pad, vec![7, 6, 0, 0, 0, 2, 1, 0]);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Encryptor<'a> {
+    pad: &'a mut [u8],
+    offset: usize,
+    allow_partial: bool,
+    zeroize_pad: bool,
+}
+
+impl<'a> Encryptor<'a> {
+    pub fn new(pad: &'a mut [u8]) -> Self {
+        Encryptor {
+            pad,
+            offset: 0,
+            allow_partial: false,
+            zeroize_pad: false,
+        }
+    }
+
+    /// Starts at pad byte `offset` instead of the first. Fails with
+    /// `OutOfPad` if the pad is too short. Defaults to 0.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Allows a pad longer than the data. Defaults to `false`.
+    pub fn allow_partial(mut self, allow_partial: bool) -> Self {
+        self.allow_partial = allow_partial;
+        self
+    }
+
+    /// Wipes the used pad bytes after encrypting. Defaults to `false`.
+    pub fn zeroize_pad(mut self, zeroize_pad: bool) -> Self {
+        self.zeroize_pad = zeroize_pad;
+        self
+    }
+
+    pub fn encrypt(self, plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        self.operate(plain_text_buffer)
+    }
+
+    /// Decrypts with the same options; XOR makes it the same operation.
+    pub fn decrypt(self, encrypted_data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        self.operate(encrypted_data_buffer)
+    }
+
+    fn operate(self, data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        let length = if self.allow_partial {
+            data_buffer.len()
+        } else {
+            self.pad.len().saturating_sub(self.offset)
+        };
+        let region = pad_segment(self.pad, self.offset, length)?;
+        error_check(region, data_buffer)?;
+
+        let mut result = data_buffer.to_vec();
+        xor(region, &mut result);
+        if self.zeroize_pad {
+            wipe(&mut self.pad[self.offset..self.offset + length]);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{Encryptor, OneTimePad, OtpError};
+
+    #[test]
+    fn defaults_match_encrypt() {
+        let mut pad = vec![7, 6, 5, 4];
+
+        let encrypted_data = Encryptor::new(&mut pad).encrypt(&[1, 2, 3, 4]).unwrap();
+
+        assert_eq!(encrypted_data, OneTimePad::encrypt([7, 6, 5, 4], [1, 2, 3, 4]).unwrap());
+        assert_eq!(pad, vec![7, 6, 5, 4]);
+        assert!(matches!(
+            Encryptor::new(&mut pad).encrypt(&[1, 2, 3]),
+            Err(OtpError::LengthMismatch { pad_len: 4, data_len: 3 })
+        ));
+    }
+
+    #[test]
+    fn offset_and_partial_match_encrypt_with_offset() {
+        let mut pad: Vec<u8> = (0..12).collect();
+        let expected = OneTimePad::encrypt_with_offset(&pad, &[9; 4], 3).unwrap();
+
+        let strict = Encryptor::new(&mut pad).offset(3).encrypt(&[9; 4]);
+        let partial = Encryptor::new(&mut pad).offset(3).allow_partial(true).encrypt(&[9; 4]);
+        let rest = Encryptor::new(&mut pad).offset(8).encrypt(&[9; 4]).unwrap();
+
+        assert!(matches!(strict, Err(OtpError::LengthMismatch { pad_len: 9, data_len: 4 })));
+        assert_eq!(partial.unwrap(), expected);
+        assert_eq!(rest, OneTimePad::encrypt([8, 9, 10, 11], [9; 4]).unwrap());
+        assert!(matches!(
+            Encryptor::new(&mut pad).offset(10).allow_partial(true).encrypt(&[9; 4]),
+            Err(OtpError::OutOfPad { offset: 10, needed: 4, available: 2 })
+        ));
+    }
+
+    #[test]
+    fn zeroize_wipes_only_used_bytes_on_success() {
+        let mut pad = vec![0xff; 8];
+
+        let failed = Encryptor::new(&mut pad).zeroize_pad(true).encrypt(&[1; 7]);
+        assert!(failed.is_err());
+        assert_eq!(pad, vec![0xff; 8]);
+
+        let encrypted_data = Encryptor::new(&mut pad)
+            .offset(4)
+            .allow_partial(true)
+            .zeroize_pad(true)
+            .encrypt(&[0; 2])
+            .unwrap();
+        assert_eq!(encrypted_data, vec![0xff; 2]);
+        assert_eq!(pad, vec![0xff, 0xff, 0xff, 0xff, 0, 0, 0xff, 0xff]);
+
+        let decrypted_data = Encryptor::new(&mut [0xff, 0xff]).decrypt(&encrypted_data).unwrap();
+        assert_eq!(decrypted_data, vec![0; 2]);
+    }
+}
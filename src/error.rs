@@ -0,0 +1,244 @@
+#[cfg(feature = "alloc")]
+use alloc::string::{FromUtf8Error, String};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use crate::FormatError;
+#[cfg(feature = "alloc")]
+use crate::PaperError;
+use crate::{HealthReport, SourceError};
+#[cfg(feature = "std")]
+use std::io;
+
+/// Errors returned by the fallible one-time-pad operations.
+///
+/// Some variants only exist with certain features enabled, and enabling a
+/// feature anywhere in the dependency graph adds them, so matches on this
+/// enum need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OtpError {
+    /// The pad and data buffers have different lengths.
+    LengthMismatch { pad_len: usize, data_len: usize },
+    /// The pad or data buffer is empty.
+    EmptyBuffer,
+    /// A pad of zero bytes was requested.
+    ZeroLength,
+    /// The pad does not have `needed` bytes left at `offset`.
+    OutOfPad {
+        offset: usize,
+        needed: usize,
+        available: usize,
+    },
+    /// The output buffer is too small to hold the result.
+    OutputTooSmall { needed: usize, available: usize },
+    /// The output buffer is not the same length as the data.
+    OutputLengthMismatch { data_len: usize, output_len: usize },
+    /// The pad has already been used for an earlier message.
+    PadReused,
+    /// Fewer than `needed` unused pad bytes remain.
+    PadExhausted { needed: usize, remaining: usize },
+    /// The requested pad region overlaps bytes that have already been used.
+    RegionAlreadyUsed { offset: usize, consumed: usize },
+    /// A hex string has an odd number of digits.
+    InvalidHexLength { length: usize },
+    /// A hex string contains a character that is not a hex digit.
+    InvalidHexCharacter { character: char, index: usize },
+    /// A letter-only text or pad contains a character that is not a letter.
+    InvalidLetter { character: char, index: usize },
+    /// A digit-only text or pad contains a character that is not a digit.
+    InvalidDigit { character: char, index: usize },
+    /// A pad iterator and a data iterator have different lengths; the
+    /// shorter one ended after `shorter_len` bytes.
+    IterLengthMismatch { shorter_len: usize },
+    /// `shares` is fewer than the `minimum` number of shares: one for
+    /// `split_pad`, two for `split_secret` and `combine_shares`.
+    TooFewShares { shares: usize, minimum: usize },
+    /// A pad book already holds a pad with this id.
+    #[cfg(feature = "alloc")]
+    DuplicatePadId { id: String },
+    /// Line `line` of a pad metadata sidecar is malformed.
+    InvalidMetadata { line: usize },
+    /// A pad metadata sidecar has no `field` line.
+    MissingMetadata { field: &'static str },
+    /// An authentication tag is missing or does not match the ciphertext.
+    TamperDetected,
+    /// A memory-mapped pad file has shrunk to `found` bytes since it was
+    /// mapped with `expected`.
+    PadFileTruncated { expected: u64, found: u64 },
+    /// A freshly generated pad failed `PadHealth::check`.
+    UnhealthyPad(HealthReport),
+    /// Decrypted data is not valid UTF-8 text.
+    #[cfg(feature = "alloc")]
+    InvalidUtf8(FromUtf8Error),
+    /// A printed pad could not be parsed.
+    #[cfg(feature = "alloc")]
+    Paper(PaperError),
+    /// An entropy source could not supply pad material. Random number
+    /// generator failures are reported as `Rng` instead.
+    Source(SourceError),
+    /// An encrypted container is malformed.
+    #[cfg(feature = "std")]
+    Format(FormatError),
+    /// A base64 string could not be decoded.
+    #[cfg(feature = "base64")]
+    InvalidBase64(base64::DecodeError),
+    /// Deriving a pad from a passphrase failed, e.g. because the salt is too
+    /// short.
+    #[cfg(feature = "kdf")]
+    Kdf(argon2::Error),
+    /// The random number generator failed.
+    Rng(getrandom::Error),
+    /// Reading or writing pad material failed.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl fmt::Display for OtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OtpError::LengthMismatch { pad_len, data_len } => write!(
+                f,
+                "pad length ({}) does not match data length ({})",
+                pad_len, data_len
+            ),
+            OtpError::EmptyBuffer => write!(f, "pad and data buffers cannot be empty"),
+            OtpError::ZeroLength => write!(f, "pad length must be greater than zero"),
+            OtpError::OutOfPad {
+                offset,
+                needed,
+                available,
+            } => write!(
+                f,
+                "pad has {} bytes available at offset {} but {} are needed",
+                available, offset, needed
+            ),
+            OtpError::OutputTooSmall { needed, available } => write!(
+                f,
+                "output buffer holds {} bytes but {} are needed",
+                available, needed
+            ),
+            OtpError::OutputLengthMismatch { data_len, output_len } => write!(
+                f,
+                "output length ({}) does not match data length ({})",
+                output_len, data_len
+            ),
+            OtpError::PadReused => write!(f, "pad has already been used"),
+            OtpError::PadExhausted { needed, remaining } => write!(
+                f,
+                "pad has {} unused bytes but {} are needed",
+                remaining, needed
+            ),
+            OtpError::RegionAlreadyUsed { offset, consumed } => write!(
+                f,
+                "pad region at offset {} overlaps the {} bytes already used",
+                offset, consumed
+            ),
+            OtpError::InvalidHexLength { length } => {
+                write!(f, "hex string has an odd number of digits ({})", length)
+            }
+            OtpError::InvalidHexCharacter { character, index } => write!(
+                f,
+                "invalid hex character {:?} at position {}",
+                character, index
+            ),
+            OtpError::InvalidLetter { character, index } => {
+                write!(f, "invalid letter {:?} at position {}", character, index)
+            }
+            OtpError::InvalidDigit { character, index } => {
+                write!(f, "invalid digit {:?} at position {}", character, index)
+            }
+            OtpError::IterLengthMismatch { shorter_len } => write!(
+                f,
+                "pad and data have different lengths, the shorter ended after {} bytes",
+                shorter_len
+            ),
+            OtpError::TooFewShares { shares, minimum } => {
+                write!(f, "needs at least {} shares, got {}", minimum, shares)
+            }
+            #[cfg(feature = "alloc")]
+            OtpError::DuplicatePadId { id } => write!(f, "pad book already has a pad with id {:?}", id),
+            OtpError::InvalidMetadata { line } => write!(f, "line {} of the pad metadata is malformed", line),
+            OtpError::MissingMetadata { field } => write!(f, "pad metadata has no {} field", field),
+            OtpError::TamperDetected => write!(f, "ciphertext failed authentication"),
+            OtpError::PadFileTruncated { expected, found } => write!(
+                f,
+                "pad file was truncated to {} bytes while mapped, expected {}",
+                found, expected
+            ),
+            OtpError::UnhealthyPad(_) => write!(f, "generated pad failed its randomness health checks"),
+            #[cfg(feature = "alloc")]
+            OtpError::InvalidUtf8(e) => write!(f, "decrypted data is not valid UTF-8: {}", e),
+            #[cfg(feature = "alloc")]
+            OtpError::Paper(e) => write!(f, "invalid printed pad: {}", e),
+            OtpError::Source(e) => write!(f, "entropy source failed: {}", e),
+            #[cfg(feature = "std")]
+            OtpError::Format(e) => write!(f, "invalid container: {}", e),
+            #[cfg(feature = "base64")]
+            OtpError::InvalidBase64(e) => write!(f, "invalid base64: {}", e),
+            #[cfg(feature = "kdf")]
+            OtpError::Kdf(e) => write!(f, "pad derivation failed: {}", e),
+            OtpError::Rng(e) => write!(f, "random number generator failed: {}", e),
+            #[cfg(feature = "std")]
+            OtpError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OtpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "base64")]
+            OtpError::InvalidBase64(e) => Some(e),
+            #[cfg(feature = "kdf")]
+            OtpError::Kdf(e) => Some(e),
+            OtpError::InvalidUtf8(e) => Some(e),
+            #[cfg(feature = "alloc")]
+            OtpError::Paper(e) => Some(e),
+            OtpError::Source(e) => Some(e),
+            OtpError::Format(e) => Some(e),
+            OtpError::Rng(e) => Some(e),
+            OtpError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<PaperError> for OtpError {
+    fn from(e: PaperError) -> Self {
+        OtpError::Paper(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FormatError> for OtpError {
+    fn from(e: FormatError) -> Self {
+        OtpError::Format(e)
+    }
+}
+
+/// Random number generator failures become `Rng`, the same as when the
+/// generator is called directly.
+impl From<SourceError> for OtpError {
+    fn from(e: SourceError) -> Self {
+        match e {
+            SourceError::Rng(e) => OtpError::Rng(e),
+            e => OtpError::Source(e),
+        }
+    }
+}
+
+impl From<getrandom::Error> for OtpError {
+    fn from(e: getrandom::Error) -> Self {
+        OtpError::Rng(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for OtpError {
+    fn from(e: io::Error) -> Self {
+        OtpError::Io(e)
+    }
+}
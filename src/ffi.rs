@@ -0,0 +1,165 @@
+//! C API, enabled with the `capi` feature. Build a shared library with
+//! `cargo rustc --release --features capi --crate-type cdylib` and include
+//! `include/one_time_pad.h`, which is generated by
+//! `cbindgen --config cbindgen.toml --output include/one_time_pad.h`.
+//!
+//! Every function returns `OTP_OK` (0) on success or one of the nonzero
+//! `OTP_ERROR_*` codes. Panics are caught at the boundary and reported as
+//! `OTP_ERROR_PANIC` instead of unwinding into C.
+
+use core::ptr;
+use core::slice;
+use std::os::raw::c_int;
+use std::panic::{self, UnwindSafe};
+
+use crate::OneTimePad;
+
+/// The call succeeded.
+pub const OTP_OK: c_int = 0;
+/// A pointer argument was null.
+pub const OTP_ERROR_NULL_POINTER: c_int = 1;
+/// The length argument was zero.
+pub const OTP_ERROR_EMPTY_BUFFER: c_int = 2;
+/// The random number generator failed.
+pub const OTP_ERROR_RNG: c_int = 3;
+/// The library panicked; this is a bug.
+pub const OTP_ERROR_PANIC: c_int = 99;
+
+/// XORs `len` bytes of `data` with `pad` into `out`.
+///
+/// # Safety
+///
+/// `pad` and `data` must be valid for reading and `out` valid for writing
+/// `len` bytes. `out` may be the same buffer as `data` to encrypt in place,
+/// but must not overlap `pad`.
+#[no_mangle]
+pub unsafe extern "C" fn otp_encrypt(pad: *const u8, data: *const u8, out: *mut u8, len: usize) -> c_int {
+    guard(|| xor_raw(pad, data, out, len))
+}
+
+/// Reverses `otp_encrypt`. The same rules apply.
+///
+/// # Safety
+///
+/// See `otp_encrypt`.
+#[no_mangle]
+pub unsafe extern "C" fn otp_decrypt(pad: *const u8, data: *const u8, out: *mut u8, len: usize) -> c_int {
+    guard(|| xor_raw(pad, data, out, len))
+}
+
+/// Fills `out` with `len` random pad bytes from the operating system.
+///
+/// # Safety
+///
+/// `out` must be valid for writing `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn otp_generate_pad(out: *mut u8, len: usize) -> c_int {
+    guard(|| {
+        if out.is_null() {
+            return OTP_ERROR_NULL_POINTER;
+        }
+        if len == 0 {
+            return OTP_ERROR_EMPTY_BUFFER;
+        }
+
+        match OneTimePad::generate_random_pad_into(slice::from_raw_parts_mut(out, len)) {
+            Ok(()) => OTP_OK,
+            Err(_) => OTP_ERROR_RNG,
+        }
+    })
+}
+
+unsafe fn xor_raw(pad: *const u8, data: *const u8, out: *mut u8, len: usize) -> c_int {
+    if pad.is_null() || data.is_null() || out.is_null() {
+        return OTP_ERROR_NULL_POINTER;
+    }
+    if len == 0 {
+        return OTP_ERROR_EMPTY_BUFFER;
+    }
+
+    // `copy` allows data and out to overlap, so in-place calls work.
+    ptr::copy(data, out, len);
+    match OneTimePad::xor_in_place(slice::from_raw_parts(pad, len), slice::from_raw_parts_mut(out, len)) {
+        Ok(()) => OTP_OK,
+        Err(_) => OTP_ERROR_EMPTY_BUFFER,
+    }
+}
+
+fn guard<F: FnOnce() -> c_int + UnwindSafe>(f: F) -> c_int {
+    panic::catch_unwind(f).unwrap_or(OTP_ERROR_PANIC)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::ffi::*;
+    use core::ptr;
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let mut pad = [0u8; 32];
+        let plain_text = *b"C callers get the same XOR pads.";
+        let mut encrypted_data = [0u8; 32];
+        let mut decrypted_data = [0u8; 32];
+
+        unsafe {
+            assert_eq!(otp_generate_pad(pad.as_mut_ptr(), pad.len()), OTP_OK);
+            assert_eq!(
+                otp_encrypt(pad.as_ptr(), plain_text.as_ptr(), encrypted_data.as_mut_ptr(), 32),
+                OTP_OK
+            );
+            assert_eq!(
+                otp_decrypt(pad.as_ptr(), encrypted_data.as_ptr(), decrypted_data.as_mut_ptr(), 32),
+                OTP_OK
+            );
+        }
+
+        assert_ne!(encrypted_data, plain_text);
+        assert_eq!(decrypted_data, plain_text);
+    }
+
+    #[test]
+    fn encrypts_in_place() {
+        let pad = [1u8, 2, 3];
+        let mut data = [1u8, 1, 1];
+
+        let code = unsafe { otp_encrypt(pad.as_ptr(), data.as_ptr(), data.as_mut_ptr(), data.len()) };
+
+        assert_eq!(code, OTP_OK);
+        assert_eq!(data, [0, 3, 2]);
+    }
+
+    #[test]
+    fn reports_error_codes() {
+        let pad = [1u8; 4];
+        let mut out = [0u8; 4];
+
+        unsafe {
+            assert_eq!(otp_encrypt(ptr::null(), pad.as_ptr(), out.as_mut_ptr(), 4), OTP_ERROR_NULL_POINTER);
+            assert_eq!(otp_decrypt(pad.as_ptr(), ptr::null(), out.as_mut_ptr(), 4), OTP_ERROR_NULL_POINTER);
+            assert_eq!(otp_encrypt(pad.as_ptr(), pad.as_ptr(), ptr::null_mut(), 4), OTP_ERROR_NULL_POINTER);
+            assert_eq!(otp_encrypt(pad.as_ptr(), pad.as_ptr(), out.as_mut_ptr(), 0), OTP_ERROR_EMPTY_BUFFER);
+            assert_eq!(otp_generate_pad(ptr::null_mut(), 4), OTP_ERROR_NULL_POINTER);
+            assert_eq!(otp_generate_pad(out.as_mut_ptr(), 0), OTP_ERROR_EMPTY_BUFFER);
+        }
+    }
+
+    #[test]
+    fn header_declares_every_function() {
+        let header = include_str!("../include/one_time_pad.h");
+
+        for declaration in [
+            "int otp_encrypt(const uint8_t *pad, const uint8_t *data, uint8_t *out, size_t len);",
+            "int otp_decrypt(const uint8_t *pad, const uint8_t *data, uint8_t *out, size_t len);",
+            "int otp_generate_pad(uint8_t *out, size_t len);",
+            "#define OTP_ERROR_PANIC 99",
+        ] {
+            assert!(header.contains(declaration), "header is missing {}", declaration);
+        }
+    }
+
+    #[test]
+    fn panics_become_error_codes() {
+        assert_eq!(super::guard(|| panic!("boom")), OTP_ERROR_PANIC);
+    }
+}
@@ -0,0 +1,224 @@
+use std::convert::TryInto;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{OneTimePad, OtpError, Pad};
+
+const PAD_FILE_MAGIC: &[u8; 4] = b"OTP1";
+/// Magic, little-endian u64 pad length, little-endian CRC-32 of the pad.
+const PAD_FILE_HEADER_SIZE: usize = 16;
+
+impl OneTimePad {
+    /// Loads exactly `length` bytes of pad material starting `offset` bytes
+    /// into a file, e.g. a region of a large pre-generated pad. Fails with an
+    /// `UnexpectedEof` I/O error if the file ends before the region does.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::build_pad_from_file(Path::new("pad.bin"), 1024, 6)?;
+    ///     let encrypted_data = OneTimePad::encrypt(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_pad_from_file(path: &Path, offset: u64, length: usize) -> Result<Pad, OtpError> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+
+        let mut pad = Pad::from(vec![0; length]);
+        file.read_exact(pad.as_mut_slice())?;
+        Ok(pad)
+    }
+
+    /// Writes `pad` to `path` in the pad file format: the magic bytes `OTP1`,
+    /// the pad length as a little-endian u64 and a CRC-32 of the pad, followed
+    /// by the pad itself. Replaces any existing file.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(1024).unwrap();
+    ///     OneTimePad::write_pad_file(Path::new("pad.otp"), &pad)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_pad_file(path: &Path, pad: &[u8]) -> io::Result<()> {
+        let mut header = [0u8; PAD_FILE_HEADER_SIZE];
+        header[..4].copy_from_slice(PAD_FILE_MAGIC);
+        header[4..12].copy_from_slice(&(pad.len() as u64).to_le_bytes());
+        header[12..].copy_from_slice(&crc32(pad).to_le_bytes());
+
+        let mut file = File::create(path)?;
+        file.write_all(&header)?;
+        file.write_all(pad)?;
+        file.sync_all()
+    }
+
+    /// Reads a pad written by `write_pad_file`. Fails with an `InvalidData`
+    /// I/O error if the magic bytes are wrong, the file is truncated or has
+    /// trailing bytes, or the CRC does not match.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::read_pad_file(Path::new("pad.otp"))?;
+    ///     let encrypted_data = OneTimePad::encrypt(&pad[..6], &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_pad_file(path: &Path) -> io::Result<Pad> {
+        let mut file = File::open(path)?;
+        let file_len = file.metadata()?.len();
+
+        let mut header = [0u8; PAD_FILE_HEADER_SIZE];
+        file.read_exact(&mut header)
+            .map_err(|_| invalid_pad_file("file is too short to be a pad file"))?;
+        if &header[..4] != PAD_FILE_MAGIC {
+            return Err(invalid_pad_file("not a pad file (bad magic bytes)"));
+        }
+
+        let length = u64::from_le_bytes(header[4..12].try_into().unwrap());
+        let checksum = u32::from_le_bytes(header[12..].try_into().unwrap());
+        // Check against the real file size before allocating, so a corrupt
+        // length can't trigger a huge allocation.
+        if file_len - PAD_FILE_HEADER_SIZE as u64 != length {
+            return Err(invalid_pad_file("pad length does not match the file size"));
+        }
+
+        let mut pad = Pad::from(vec![0; length as usize]);
+        file.read_exact(pad.as_mut_slice())?;
+        if crc32(&pad) != checksum {
+            return Err(invalid_pad_file("pad checksum does not match"));
+        }
+
+        Ok(pad)
+    }
+
+    /// Encrypts the file at `input` with the pad file at `pad`, streaming the
+    /// result to `output`. Returns the number of bytes encrypted.
+    ///
+    /// Fails before writing anything if the pad file is shorter than the
+    /// input, and refuses to replace an existing `output` unless `overwrite`
+    /// is set.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let encrypted = OneTimePad::encrypt_file(
+    ///         Path::new("message.txt"),
+    ///         Path::new("pad.bin"),
+    ///         Path::new("message.txt.encrypted"),
+    ///         false,
+    ///     )?;
+    ///     println!("Encrypted {} bytes", encrypted);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_file(input: &Path, pad: &Path, output: &Path, overwrite: bool) -> io::Result<u64> {
+        operate_file(input, pad, output, overwrite)
+    }
+
+    /// Decrypts the file at `input` with the pad file at `pad`, streaming the
+    /// result to `output`. See `encrypt_file`.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let decrypted = OneTimePad::decrypt_file(
+    ///         Path::new("message.txt.encrypted"),
+    ///         Path::new("pad.bin"),
+    ///         Path::new("message.txt"),
+    ///         true,
+    ///     )?;
+    ///     println!("Decrypted {} bytes", decrypted);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_file(input: &Path, pad: &Path, output: &Path, overwrite: bool) -> io::Result<u64> {
+        operate_file(input, pad, output, overwrite)
+    }
+}
+
+fn operate_file(input: &Path, pad: &Path, output: &Path, overwrite: bool) -> io::Result<u64> {
+    let input_len = fs::metadata(input)?.len();
+    let pad_len = fs::metadata(pad)?.len();
+    if pad_len < input_len {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "pad file ({} bytes) is shorter than the input ({} bytes)",
+                pad_len, input_len
+            ),
+        ));
+    }
+
+    let input_file = File::open(input)?;
+    let pad_file = File::open(pad)?;
+
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    let output_file = options.open(output)?;
+
+    OneTimePad::encrypt_stream(pad_file, input_file, output_file)
+}
+
+/// Path of a file stored next to the pad file, e.g. `pad.bin.meta` for
+/// `pad.bin` and `suffix` `.meta`.
+pub(crate) fn sidecar_path(pad_path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(pad_path);
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+fn invalid_pad_file(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+/// CRC-32 (IEEE 802.3), computed bitwise; pad files are read rarely enough
+/// that a lookup table isn't worth it.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::crc32;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+}
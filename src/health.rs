@@ -0,0 +1,218 @@
+use crate::quality::{chi_square, CHI_SQUARE_LIMIT, CHI_SQUARE_MIN_LENGTH};
+#[cfg(feature = "alloc")]
+use crate::{OneTimePad, OtpError, Pad};
+
+/// Monobit and runs statistics are squared standard normal scores. Random
+/// data scores above 36 (six standard deviations) about twice in a billion
+/// tries.
+const NORMAL_SQUARED_LIMIT: f64 = 36.0;
+
+/// Statistical health tests for pad material, to catch a broken entropy
+/// source before its output is trusted. Like `check_pad_quality`, passing
+/// can't prove a pad is random; failing means it almost certainly isn't.
+///
+/// The tests follow NIST SP 800-22: the monobit test counts one bits, the
+/// runs test counts changes between successive bits, and the byte frequency
+/// test compares how often each byte value appears. Limits are set so that
+/// a genuinely random pad fails only a few times in a billion checks.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PadHealth};
+///
+/// fn main() {
+///     let pad = OneTimePad::generate_random_pad(4096).unwrap();
+///     let report = PadHealth::check(&pad);
+///     println!("{:?}", report);
+///     assert!(report.passed());
+///     assert!(!PadHealth::check(&[0x55; 4096]).passed());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PadHealth;
+
+/// The outcome of one health test. `statistic` is compared against `limit`
+/// and the test passes when it is at most the limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthTest {
+    pub statistic: f64,
+    pub limit: f64,
+    pub passed: bool,
+}
+
+/// Results of `PadHealth::check`, for logging or deciding whether to use a
+/// pad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthReport {
+    /// Number of bytes checked.
+    pub length: usize,
+    /// Squared normal score of the difference between one and zero bits.
+    pub monobit: HealthTest,
+    /// Squared normal score of the number of runs of identical bits.
+    pub runs: HealthTest,
+    /// Chi-square statistic of the byte values, or `None` for pads under
+    /// 1280 bytes, which are too short for it.
+    pub byte_frequency: Option<HealthTest>,
+}
+
+impl HealthReport {
+    /// Whether every test that was run passed.
+    pub fn passed(&self) -> bool {
+        self.monobit.passed
+            && self.runs.passed
+            && self.byte_frequency.is_none_or(|test| test.passed)
+    }
+}
+
+impl PadHealth {
+    /// Runs every health test on `pad_buffer`. An empty buffer fails them
+    /// all.
+    pub fn check(pad_buffer: &[u8]) -> HealthReport {
+        let bits = pad_buffer.len() as f64 * 8.0;
+        let ones: u64 = pad_buffer
+            .iter()
+            .map(|byte| u64::from(byte.count_ones()))
+            .sum();
+
+        // Monobit: the sum of +1 for every one bit and -1 for every zero
+        // bit, squared and scaled by the number of bits.
+        let sum = 2.0 * ones as f64 - bits;
+        let monobit = test(sum * sum / bits, NORMAL_SQUARED_LIMIT);
+
+        // Runs: the number of runs is one more than the number of places a
+        // bit differs from the one before it.
+        let mut changes = 0u64;
+        let mut previous: Option<u8> = None;
+        for &byte in pad_buffer {
+            // Bits are read from the most significant down, so each bit is
+            // compared with the one above it, and the top bit with the
+            // bottom bit of the byte before.
+            changes += u64::from(((byte ^ (byte >> 1)) & 0x7f).count_ones());
+            if let Some(previous) = previous {
+                changes += u64::from((previous & 1) != (byte >> 7));
+            }
+            previous = Some(byte);
+        }
+        let runs = changes as f64 + 1.0;
+        let proportion = ones as f64 / bits;
+        let spread = proportion * (1.0 - proportion);
+        let difference = runs - 2.0 * bits * spread;
+        let runs = test(
+            difference * difference / (4.0 * bits * spread * spread),
+            NORMAL_SQUARED_LIMIT,
+        );
+
+        let byte_frequency = if pad_buffer.len() >= CHI_SQUARE_MIN_LENGTH {
+            Some(test(chi_square(pad_buffer), CHI_SQUARE_LIMIT))
+        } else {
+            None
+        };
+
+        HealthReport {
+            length: pad_buffer.len(),
+            monobit,
+            runs,
+            byte_frequency,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl OneTimePad {
+    /// Like `generate_random_pad`, but runs `PadHealth::check` on the new pad
+    /// and fails with `UnhealthyPad` instead of returning a pad that looks
+    /// broken.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad_checked(1 << 20)?;
+    ///     println!("Encryption Pad: {:?}", pad);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_random_pad_checked(length: usize) -> Result<Pad, OtpError> {
+        let pad = Self::generate_random_pad(length)?;
+        let report = PadHealth::check(&pad);
+        if !report.passed() {
+            return Err(OtpError::UnhealthyPad(report));
+        }
+        Ok(pad)
+    }
+}
+
+/// A statistic that is not a number, such as from an empty pad or a pad
+/// with no zero bits at all, fails.
+fn test(statistic: f64, limit: f64) -> HealthTest {
+    let statistic = if statistic.is_nan() {
+        f64::INFINITY
+    } else {
+        statistic
+    };
+    HealthTest {
+        statistic,
+        limit,
+        passed: statistic <= limit,
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+
+    use crate::{OneTimePad, OtpError, PadHealth};
+
+    #[test]
+    fn all_zero_pad_fails_every_test() {
+        let report = PadHealth::check(&[0; 4096]);
+
+        assert!(!report.passed());
+        assert!(!report.monobit.passed);
+        assert!(!report.runs.passed);
+        assert!(!report.byte_frequency.unwrap().passed);
+        assert_eq!(report.runs.statistic, f64::INFINITY);
+        assert!(!PadHealth::check(&[]).passed());
+    }
+
+    #[test]
+    fn repeating_patterns_fail() {
+        // Balanced bits, but they alternate every time.
+        let alternating = PadHealth::check(&[0x55; 4096]);
+        assert!(alternating.monobit.passed);
+        assert!(!alternating.runs.passed);
+        assert!(!alternating.passed());
+
+        let text: Vec<u8> = b"attack at dawn "
+            .iter()
+            .copied()
+            .cycle()
+            .take(4096)
+            .collect();
+        let report = PadHealth::check(&text);
+        assert!(!report.byte_frequency.unwrap().passed);
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn random_pads_pass() {
+        let pad = OneTimePad::generate_random_pad(1 << 20).unwrap();
+        let report = PadHealth::check(&pad);
+
+        assert!(report.passed(), "{:?}", report);
+        assert_eq!(report.length, 1 << 20);
+
+        let short = PadHealth::check(&OneTimePad::generate_random_pad(64).unwrap());
+        assert!(short.byte_frequency.is_none());
+        assert!(short.passed(), "{:?}", short);
+    }
+
+    #[test]
+    fn checked_generation_returns_healthy_pads() {
+        let pad = OneTimePad::generate_random_pad_checked(4096).unwrap();
+        assert_eq!(pad.len(), 4096);
+        assert!(matches!(
+            OneTimePad::generate_random_pad_checked(0),
+            Err(OtpError::ZeroLength)
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::OtpError;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Encodes bytes as lowercase hex.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        hex.push(HEX_DIGITS[usize::from(byte >> 4)] as char);
+        hex.push(HEX_DIGITS[usize::from(byte & 0x0f)] as char);
+    }
+    hex
+}
+
+/// Decodes upper or lowercase hex, reporting the position of the first
+/// character that is not a hex digit.
+pub(crate) fn decode(hex: &str) -> Result<Vec<u8>, OtpError> {
+    let mut nibbles = Vec::with_capacity(hex.len());
+    for (index, character) in hex.chars().enumerate() {
+        match character.to_digit(16) {
+            Some(nibble) => nibbles.push(nibble as u8),
+            None => return Err(OtpError::InvalidHexCharacter { character, index }),
+        }
+    }
+
+    if nibbles.len() % 2 != 0 {
+        return Err(OtpError::InvalidHexLength { length: nibbles.len() });
+    }
+
+    Ok(nibbles.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::hex::{decode, encode};
+    use crate::OtpError;
+
+    #[test]
+    fn known_answer() {
+        assert_eq!(encode(&[0x00, 0x0f, 0xa5, 0xff]), "000fa5ff");
+        assert_eq!(decode("000fa5ff").unwrap(), vec![0x00, 0x0f, 0xa5, 0xff]);
+        assert_eq!(decode("000FA5FF").unwrap(), vec![0x00, 0x0f, 0xa5, 0xff]);
+    }
+
+    #[test]
+    fn round_trips_every_byte() {
+        let bytes: Vec<u8> = (0..=255).collect();
+
+        assert_eq!(decode(&encode(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn rejects_odd_length() {
+        assert!(matches!(decode("abc"), Err(OtpError::InvalidHexLength { length: 3 })));
+    }
+
+    #[test]
+    fn reports_position_of_invalid_character() {
+        assert!(matches!(
+            decode("00g0"),
+            Err(OtpError::InvalidHexCharacter { character: 'g', index: 2 })
+        ));
+        assert!(matches!(
+            decode("0é"),
+            Err(OtpError::InvalidHexCharacter { character: 'é', index: 1 })
+        ));
+    }
+}
@@ -0,0 +1,356 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::xor;
+
+pub(crate) const WRITE_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Wraps a reader and XORs everything read from it against a pad, so data is
+/// encrypted or decrypted as it is read.
+///
+/// Reading fails with `UnexpectedEof` if the inner reader still has data once
+/// the pad has been used up.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PadReader};
+/// use std::error::Error;
+/// use std::io;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+///     let mut encrypted_data = Vec::new();
+///     io::copy(&mut PadReader::new(&[1, 2, 3, 4, 5, 6][..], &pad), &mut encrypted_data)?;
+///     println!("Encrypted Data: {:?}", encrypted_data);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PadReader<'a, R> {
+    inner: R,
+    pad: &'a [u8],
+    position: usize,
+}
+
+impl<'a, R: Read> PadReader<'a, R> {
+    pub fn new(inner: R, pad: &'a [u8]) -> Self {
+        PadReader {
+            inner,
+            pad,
+            position: 0,
+        }
+    }
+
+    /// Number of pad bytes used so far, which is also the number of bytes
+    /// read.
+    pub fn consumed(&self) -> usize {
+        self.position
+    }
+
+    /// Number of pad bytes still available for reading.
+    pub fn remaining(&self) -> usize {
+        self.pad.len() - self.position
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for PadReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let remaining = &self.pad[self.position..];
+        if remaining.is_empty() {
+            // The pad is used up, which is only fine if the data is too.
+            let mut probe = [0u8; 1];
+            return match self.inner.read(&mut probe)? {
+                0 => Ok(0),
+                _ => Err(pad_exhausted()),
+            };
+        }
+
+        let limit = buf.len().min(remaining.len());
+        let read = self.inner.read(&mut buf[..limit])?;
+        xor(&remaining[..read], &mut buf[..read]);
+        self.position += read;
+
+        Ok(read)
+    }
+}
+
+/// Wraps a writer and XORs everything written to it against a pad before
+/// passing it on, so data is encrypted or decrypted as it is written.
+///
+/// The pad is only advanced by the bytes the inner writer actually accepts.
+/// Writing fails with `UnexpectedEof` once the pad has been used up.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PadWriter};
+/// use std::error::Error;
+/// use std::io::Write;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+///     let mut encrypted_data = Vec::new();
+///     PadWriter::new(&mut encrypted_data, &pad).write_all(&[1, 2, 3, 4, 5, 6])?;
+///     println!("Encrypted Data: {:?}", encrypted_data);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PadWriter<'a, W> {
+    inner: W,
+    pad: &'a [u8],
+    position: usize,
+}
+
+impl<'a, W: Write> PadWriter<'a, W> {
+    pub fn new(inner: W, pad: &'a [u8]) -> Self {
+        PadWriter {
+            inner,
+            pad,
+            position: 0,
+        }
+    }
+
+    /// Number of pad bytes used so far, which is also the number of bytes
+    /// the inner writer has accepted.
+    pub fn consumed(&self) -> usize {
+        self.position
+    }
+
+    /// Number of pad bytes still available for writing.
+    pub fn remaining(&self) -> usize {
+        self.pad.len() - self.position
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for PadWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let remaining = &self.pad[self.position..];
+        if remaining.is_empty() {
+            return Err(pad_exhausted());
+        }
+
+        let mut buffer = [0u8; WRITE_BUFFER_SIZE];
+        let length = buf.len().min(remaining.len()).min(buffer.len());
+        let buffer = &mut buffer[..length];
+        buffer.copy_from_slice(&buf[..length]);
+        xor(&remaining[..length], buffer);
+
+        let written = self.inner.write(buffer);
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(buffer);
+        let written = written?;
+        self.position += written;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub(crate) fn pad_exhausted() -> io::Error {
+    io::Error::new(ErrorKind::UnexpectedEof, "pad ended before the data")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, PadReader, PadWriter};
+    use std::io::{self, BufRead, ErrorKind, Read, Write};
+
+    struct ChunkedReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let read = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..read].copy_from_slice(&self.data[..read]);
+            self.data = &self.data[read..];
+            Ok(read)
+        }
+    }
+
+    struct ChunkedWriter {
+        data: Vec<u8>,
+        chunk: usize,
+    }
+
+    impl Write for ChunkedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let written = self.chunk.min(buf.len());
+            self.data.extend_from_slice(&buf[..written]);
+            Ok(written)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reader_matches_encrypt_for_awkward_chunk_sizes() {
+        let plain_text: Vec<u8> = (0..1000u32).map(|i| (i % 253) as u8).collect();
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+        let expected = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+
+        for &chunk in &[1, 7, 64, 1000] {
+            let inner = ChunkedReader {
+                data: &plain_text,
+                chunk,
+            };
+            let mut encrypted_data = Vec::new();
+            io::copy(&mut PadReader::new(inner, &pad), &mut encrypted_data).unwrap();
+
+            assert_eq!(encrypted_data, expected);
+        }
+    }
+
+    #[test]
+    fn reader_round_trips() {
+        let plain_text = b"The quick brown fox jumps over the lazy dog";
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+
+        let encrypted = PadReader::new(&plain_text[..], &pad);
+        let mut decrypted_data = Vec::new();
+        PadReader::new(encrypted, &pad)
+            .read_to_end(&mut decrypted_data)
+            .unwrap();
+
+        assert_eq!(decrypted_data, plain_text);
+    }
+
+    #[test]
+    fn reader_errors_when_pad_runs_out() {
+        let inner = ChunkedReader {
+            data: &[0; 10],
+            chunk: 7,
+        };
+        let mut reader = PadReader::new(inner, &[1; 8]);
+
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn reader_accepts_pad_longer_than_data() {
+        let mut reader = PadReader::new(&[0, 0][..], &[5, 6, 7]);
+
+        let mut encrypted_data = Vec::new();
+        reader.read_to_end(&mut encrypted_data).unwrap();
+
+        assert_eq!(encrypted_data, vec![5, 6]);
+    }
+
+    #[test]
+    fn reader_tracks_pad_consumption() {
+        let inner = ChunkedReader {
+            data: &[0; 10],
+            chunk: 3,
+        };
+        let mut reader = PadReader::new(inner, &[1; 12]);
+        assert_eq!((reader.consumed(), reader.remaining()), (0, 12));
+
+        assert_eq!(reader.read(&mut [0; 8]).unwrap(), 3);
+        assert_eq!((reader.consumed(), reader.remaining()), (3, 9));
+
+        reader.read_to_end(&mut Vec::new()).unwrap();
+        assert_eq!((reader.consumed(), reader.remaining()), (10, 2));
+    }
+
+    #[test]
+    fn reader_decrypts_through_buffered_lines() {
+        let plain_text = "first line\nsecond line\nthird line\n";
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+        let encrypted_data = OneTimePad::encrypt(&pad, plain_text.as_bytes()).unwrap();
+
+        let reader = io::BufReader::new(PadReader::new(&encrypted_data[..], &pad));
+        let lines: Vec<String> = reader.lines().collect::<io::Result<_>>().unwrap();
+
+        assert_eq!(lines, vec!["first line", "second line", "third line"]);
+    }
+
+    #[test]
+    fn writer_round_trips_through_short_writes() {
+        let plain_text: Vec<u8> = (0..1000u32).map(|i| (i % 253) as u8).collect();
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+
+        let inner = ChunkedWriter {
+            data: Vec::new(),
+            chunk: 3,
+        };
+        let mut writer = PadWriter::new(inner, &pad);
+        writer.write_all(&plain_text[..500]).unwrap();
+        writer.write_all(&plain_text[500..]).unwrap();
+        writer.flush().unwrap();
+
+        let encrypted_data = writer.into_inner().data;
+
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+        assert_eq!(OneTimePad::decrypt(&pad, &encrypted_data).unwrap(), plain_text);
+    }
+
+    #[test]
+    fn writer_errors_when_pad_runs_out() {
+        let mut encrypted_data = Vec::new();
+        let mut writer = PadWriter::new(&mut encrypted_data, &[1, 2, 3]);
+
+        let error = writer.write_all(&[0, 0, 0, 0]).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(encrypted_data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn writer_tracks_pad_consumption() {
+        let pad = OneTimePad::generate_random_pad(10).unwrap();
+        let inner = ChunkedWriter {
+            data: Vec::new(),
+            chunk: 2,
+        };
+        let mut writer = PadWriter::new(inner, &pad);
+        assert_eq!((writer.consumed(), writer.remaining()), (0, 10));
+
+        // A short write only uses the pad for what the inner writer took.
+        assert_eq!(writer.write(&[0; 5]).unwrap(), 2);
+        assert_eq!((writer.consumed(), writer.remaining()), (2, 8));
+
+        writer.write_all(&[0; 8]).unwrap();
+        assert_eq!((writer.consumed(), writer.remaining()), (10, 0));
+        assert!(writer.write(&[0]).is_err());
+        assert_eq!(writer.into_inner().data, pad.as_slice());
+    }
+
+    #[test]
+    fn writer_nests_inside_other_writers() {
+        let plain_text = b"The quick brown fox jumps over the lazy dog";
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+
+        let mut encrypted_data = Vec::new();
+        {
+            let mut writer = io::BufWriter::new(PadWriter::new(&mut encrypted_data, &pad));
+            for word in plain_text.split_inclusive(|&b| b == b' ') {
+                writer.write_all(word).unwrap();
+            }
+            writer.flush().unwrap();
+        }
+
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, plain_text).unwrap());
+    }
+}
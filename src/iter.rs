@@ -0,0 +1,146 @@
+use core::iter::{FusedIterator, Zip};
+
+use crate::OtpError;
+
+/// Iterator returned by `OneTimePad::xor_iter`. Yields `pad ^ data` for each
+/// pair of bytes and ends as soon as either input does.
+#[derive(Debug, Clone)]
+pub struct XorIter<P, D> {
+    inner: Zip<P, D>,
+}
+
+impl<P: Iterator<Item = u8>, D: Iterator<Item = u8>> XorIter<P, D> {
+    pub(crate) fn new(pad: P, data: D) -> Self {
+        XorIter { inner: pad.zip(data) }
+    }
+}
+
+impl<P: Iterator<Item = u8>, D: Iterator<Item = u8>> Iterator for XorIter<P, D> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.inner.next().map(|(pad, data)| pad ^ data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<P: ExactSizeIterator<Item = u8>, D: ExactSizeIterator<Item = u8>> ExactSizeIterator for XorIter<P, D> {}
+
+impl<P: FusedIterator<Item = u8>, D: FusedIterator<Item = u8>> FusedIterator for XorIter<P, D> {}
+
+/// Iterator returned by `OneTimePad::xor_iter_strict`. Yields `Ok(pad ^ data)`
+/// for each pair of bytes. If one input ends before the other it yields a
+/// single `IterLengthMismatch` error and then stops.
+#[derive(Debug, Clone)]
+pub struct StrictXorIter<P, D> {
+    pad: P,
+    data: D,
+    position: usize,
+    done: bool,
+}
+
+impl<P: Iterator<Item = u8>, D: Iterator<Item = u8>> StrictXorIter<P, D> {
+    pub(crate) fn new(pad: P, data: D) -> Self {
+        StrictXorIter {
+            pad,
+            data,
+            position: 0,
+            done: false,
+        }
+    }
+}
+
+impl<P: Iterator<Item = u8>, D: Iterator<Item = u8>> Iterator for StrictXorIter<P, D> {
+    type Item = Result<u8, OtpError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match (self.pad.next(), self.data.next()) {
+            (Some(pad), Some(data)) => {
+                self.position += 1;
+                Some(Ok(pad ^ data))
+            }
+            (None, None) => {
+                self.done = true;
+                None
+            }
+            _ => {
+                self.done = true;
+                Some(Err(OtpError::IterLengthMismatch {
+                    shorter_len: self.position,
+                }))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        // One item per pair, plus an error if the lengths differ.
+        let (pad_lower, pad_upper) = self.pad.size_hint();
+        let (data_lower, data_upper) = self.data.size_hint();
+        let lower = pad_lower.min(data_lower) + usize::from(pad_lower != data_lower);
+        let upper = match (pad_upper, data_upper) {
+            (Some(pad), Some(data)) => pad.min(data).checked_add(usize::from(pad != data)),
+            (Some(bound), None) | (None, Some(bound)) => bound.checked_add(1),
+            (None, None) => None,
+        };
+        (lower, upper)
+    }
+}
+
+impl<P: ExactSizeIterator<Item = u8>, D: ExactSizeIterator<Item = u8>> ExactSizeIterator for StrictXorIter<P, D> {}
+
+impl<P: Iterator<Item = u8>, D: Iterator<Item = u8>> FusedIterator for StrictXorIter<P, D> {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+
+    use crate::{OneTimePad, OtpError};
+
+    #[test]
+    fn xor_iter_is_exact_size_for_exact_inputs() {
+        let iter = OneTimePad::xor_iter(vec![1, 2, 3], vec![4, 5, 6, 7]);
+        assert_eq!(iter.len(), 3);
+
+        let iter = OneTimePad::xor_iter_strict(vec![1, 2, 3], vec![4, 5, 6]);
+        assert_eq!(iter.len(), 3);
+
+        let mut iter = OneTimePad::xor_iter_strict(vec![1, 2, 3], vec![4, 5, 6, 7]);
+        assert_eq!(iter.len(), 4);
+        iter.next();
+        assert_eq!(iter.len(), 3);
+    }
+
+    #[test]
+    fn strict_xor_iter_matches_encrypt() {
+        let pad = OneTimePad::generate_random_pad(100).unwrap();
+        let plain_text: Vec<u8> = (0..100).collect();
+
+        let encrypted_data: Result<Vec<u8>, OtpError> =
+            OneTimePad::xor_iter_strict(pad.iter().copied(), plain_text.iter().copied()).collect();
+
+        assert_eq!(encrypted_data.unwrap(), OneTimePad::encrypt(&pad, &plain_text).unwrap());
+    }
+
+    #[test]
+    fn strict_xor_iter_reports_unequal_lengths_once() {
+        let mut short_pad = OneTimePad::xor_iter_strict(vec![1, 2], vec![0, 0, 0]);
+        assert_eq!(short_pad.next().unwrap().unwrap(), 1);
+        assert_eq!(short_pad.next().unwrap().unwrap(), 2);
+        assert!(matches!(short_pad.next(), Some(Err(OtpError::IterLengthMismatch { shorter_len: 2 }))));
+        assert!(short_pad.next().is_none());
+
+        let short_data: Result<Vec<u8>, OtpError> =
+            OneTimePad::xor_iter_strict(core::iter::repeat(1), 0..4).collect();
+        assert!(matches!(short_data, Err(OtpError::IterLengthMismatch { shorter_len: 4 })));
+    }
+}
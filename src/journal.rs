@@ -0,0 +1,89 @@
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+
+use crate::file::sidecar_path;
+use crate::{OtpError, Pad, PadState};
+
+impl PadState {
+    /// Opens the pad file at `path` with durable usage tracking. How much of
+    /// the pad has been used is kept in a journal next to it, `pad.bin.journal`
+    /// for `pad.bin`, and picked up again here, so a restarted process can't
+    /// hand out the same bytes twice.
+    ///
+    /// The journal is written and synced to disk before each region is
+    /// handed out, so a crash can waste pad bytes but never reuse them.
+    /// Fails with `PadExhausted` if the journal shows the whole pad used.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::PadState;
+    /// use std::error::Error;
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let mut state = PadState::open(Path::new("pad.bin"))?;
+    ///     let encrypted_data = state.encrypt(&[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}, {} pad bytes left", encrypted_data, state.remaining());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open(path: &Path) -> Result<Self, OtpError> {
+        Self::open_with_journal(path, &sidecar_path(path, ".journal"))
+    }
+
+    /// Like `open`, but keeps the journal at `journal` instead of next to the
+    /// pad file, e.g. on a different disk from a read-only pad.
+    pub fn open_with_journal(path: &Path, journal: &Path) -> Result<Self, OtpError> {
+        let pad = Pad::from(fs::read(path)?);
+        let consumed = read_journal(journal)?;
+
+        if consumed > pad.len() {
+            return Err(invalid_journal("journal records more bytes used than the pad holds").into());
+        }
+        if consumed == pad.len() {
+            return Err(OtpError::PadExhausted { needed: 1, remaining: 0 });
+        }
+
+        Ok(PadState::journaled(pad, consumed, journal.to_path_buf()))
+    }
+}
+
+/// The journal holds the number of pad bytes used from the start of the pad
+/// as a decimal line; `PadState` never hands out bytes before that point.
+fn read_journal(journal: &Path) -> io::Result<usize> {
+    match fs::read_to_string(journal) {
+        Ok(text) => text
+            .trim()
+            .parse()
+            .map_err(|_| invalid_journal("pad journal is not a byte count")),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e),
+    }
+}
+
+/// Records `consumed` by writing a temporary file and renaming it over the
+/// journal, so a crash leaves either the old count or the new one.
+pub(crate) fn write_journal(journal: &Path, consumed: usize) -> io::Result<()> {
+    let temporary = sidecar_path(journal, ".tmp");
+    let mut file = File::create(&temporary)?;
+    writeln!(file, "{}", consumed)?;
+    file.sync_all()?;
+    fs::rename(&temporary, journal)?;
+
+    // Sync the directory too, so the rename itself survives a power cut. A
+    // bare file name such as `pad.bin.journal` has an empty parent, which
+    // means the current directory.
+    #[cfg(unix)]
+    {
+        let directory = journal
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        File::open(directory)?.sync_all()?;
+    }
+    Ok(())
+}
+
+fn invalid_journal(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
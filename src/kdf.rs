@@ -0,0 +1,92 @@
+use alloc::vec;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+
+use crate::{OneTimePad, OtpError, Pad};
+
+// Argon2id with the OWASP recommended minimum cost: 19 MiB, two passes, one
+// lane. Changing these changes every derived pad, so they are fixed.
+const MEMORY_KIB: u32 = 19 * 1024;
+const PASSES: u32 = 2;
+const LANES: u32 = 1;
+
+impl OneTimePad {
+    /// **Not a one-time pad.** Stretches a passphrase into `length` pad-like
+    /// bytes with Argon2id, so a pad can be re-created from something a
+    /// person can remember.
+    ///
+    /// The result is a keystream: encrypting with it is a stream cipher whose
+    /// security rests entirely on the passphrase, never the information
+    /// theoretic secrecy of a true one-time pad. A guessable passphrase makes
+    /// everything encrypted with it guessable, and the same passphrase and
+    /// salt always give the same bytes, so use a fresh salt for every
+    /// message. Prefer `generate_random_pad` whenever a pad can be shared.
+    ///
+    /// The salt must be at least 8 bytes. Fails with `ZeroLength` if `length`
+    /// is zero.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::derive_pad(b"correct horse battery staple", b"message-0001", 6)?;
+    ///     let encrypted_data = OneTimePad::encrypt(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     let same_pad = OneTimePad::derive_pad(b"correct horse battery staple", b"message-0001", 6)?;
+    ///     assert_eq!(OneTimePad::decrypt(&same_pad, &encrypted_data)?, vec![1, 2, 3, 4, 5, 6]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn derive_pad(passphrase: &[u8], salt: &[u8], length: usize) -> Result<Pad, OtpError> {
+        if length == 0 {
+            return Err(OtpError::ZeroLength);
+        }
+
+        let params = Params::new(MEMORY_KIB, PASSES, LANES, None).map_err(OtpError::Kdf)?;
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+        // Argon2 can't produce fewer than four bytes, so short pads are cut
+        // from a four byte output.
+        let mut pad = Pad::from(vec![0; length.max(Params::MIN_OUTPUT_LEN)]);
+        argon2
+            .hash_password_into(passphrase, salt, pad.as_mut_slice())
+            .map_err(OtpError::Kdf)?;
+        if pad.len() > length {
+            pad = Pad::from(pad[..length].to_vec());
+        }
+
+        Ok(pad)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, OtpError};
+
+    #[test]
+    fn derived_pads_depend_on_passphrase_and_salt() {
+        let pad = OneTimePad::derive_pad(b"passphrase", b"salt-0001", 64).unwrap();
+
+        assert_eq!(pad.len(), 64);
+        let again = OneTimePad::derive_pad(b"passphrase", b"salt-0001", 64).unwrap();
+        assert_eq!(pad.as_slice(), again.as_slice());
+        let other_salt = OneTimePad::derive_pad(b"passphrase", b"salt-0002", 64).unwrap();
+        assert_ne!(pad.as_slice(), other_salt.as_slice());
+        let other_passphrase = OneTimePad::derive_pad(b"passphrasf", b"salt-0001", 64).unwrap();
+        assert_ne!(pad.as_slice(), other_passphrase.as_slice());
+    }
+
+    #[test]
+    fn derive_pad_handles_short_lengths_and_bad_salts() {
+        assert_eq!(OneTimePad::derive_pad(b"passphrase", b"salt-0001", 1).unwrap().len(), 1);
+        assert!(matches!(
+            OneTimePad::derive_pad(b"passphrase", b"salt-0001", 0),
+            Err(OtpError::ZeroLength)
+        ));
+        assert!(matches!(
+            OneTimePad::derive_pad(b"passphrase", b"short", 16),
+            Err(OtpError::Kdf(_))
+        ));
+    }
+}
@@ -1,206 +1,2318 @@
 //! This crate is a simple XOR based one-time-pad operating on byte vectors
+//!
+//! The default `std` feature adds file and stream support. Without it the
+//! crate is `no_std`; the `alloc` feature brings back everything that returns
+//! a `Vec`, `String` or `Pad`. With neither, the in-place and `_into`
+//! functions still work on caller-provided buffers without allocating.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 extern crate getrandom;
 
-#[derive(Debug)]
-pub struct OneTimePad;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryInto;
+#[cfg(feature = "alloc")]
+use core::ops::BitXor;
+use core::ptr;
+
+#[cfg(feature = "alloc")]
+mod alpha;
+#[cfg(feature = "tokio")]
+mod async_file;
+#[cfg(feature = "tokio")]
+mod async_io;
+#[cfg(feature = "alloc")]
+mod book;
+#[cfg(feature = "serde")]
+mod bytes;
+#[cfg(feature = "std")]
+mod container;
+#[cfg(feature = "alloc")]
+mod context;
+#[cfg(feature = "alloc")]
+mod digits;
+#[cfg(feature = "base64")]
+mod encoding;
+#[cfg(feature = "alloc")]
+mod encryptor;
+mod error;
+#[cfg(feature = "capi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod file;
+mod health;
+#[cfg(feature = "alloc")]
+mod hex;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+#[cfg(feature = "std")]
+mod journal;
+#[cfg(feature = "kdf")]
+mod kdf;
+mod mac;
+#[cfg(feature = "mmap")]
+mod mapped;
+#[cfg(feature = "alloc")]
+mod message;
+#[cfg(feature = "mlock")]
+mod lock;
+#[cfg(feature = "std")]
+mod metadata;
+#[cfg(feature = "alloc")]
+mod pad;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "alloc")]
+mod paper;
+mod quality;
+#[cfg(feature = "secrecy")]
+mod secret;
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+mod simd;
+mod source;
+#[cfg(feature = "alloc")]
+mod state;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod tag;
+#[cfg(feature = "alloc")]
+mod tracker;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod wipe;
+
+#[cfg(feature = "alloc")]
+pub use alpha::NonLetters;
+#[cfg(feature = "tokio")]
+pub use async_io::{AsyncPadReader, AsyncPadWriter};
+#[cfg(feature = "alloc")]
+pub use book::{PadBook, PadSegment};
+#[cfg(feature = "std")]
+pub use container::{ContainerHeader, FormatError};
+#[cfg(feature = "alloc")]
+pub use context::OtpContext;
+#[cfg(feature = "alloc")]
+pub use encryptor::Encryptor;
+pub use error::OtpError;
+pub use health::{HealthReport, HealthTest, PadHealth};
+#[cfg(feature = "std")]
+pub use io::{PadReader, PadWriter};
+pub use iter::{StrictXorIter, XorIter};
+pub use mac::{MAC_KEY_SIZE, MAC_TAG_SIZE};
+#[cfg(feature = "mmap")]
+pub use mapped::MappedPad;
+#[cfg(feature = "alloc")]
+pub use message::EncryptedMessage;
+#[cfg(feature = "std")]
+pub use metadata::PadMetadata;
+#[cfg(feature = "alloc")]
+pub use pad::Pad;
+#[cfg(feature = "alloc")]
+pub use paper::{PaperError, PaperFormat};
+pub use quality::PadQualityError;
+#[cfg(feature = "std")]
+pub use source::ReaderSource;
+pub use source::{EntropySource, GetrandomSource, PadSource, SourceError};
+#[cfg(feature = "alloc")]
+pub use state::PadState;
+#[cfg(feature = "alloc")]
+pub use tracker::PadTracker;
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+use simd::xor;
+
+const WORD_SIZE: usize = 8;
+
+#[derive(Debug)]
+pub struct OneTimePad;
+
+impl OneTimePad {
+
+    /// Returns an error if the buffers are empty or their lengths differ.
+    ///
+    /// Both arguments can be anything that views as bytes: slices, arrays,
+    /// byte strings, `Vec<u8>` or `Pad`, borrowed or owned.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt(&pad, &vec![1,2,3,4,5,6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     let encrypted_literal = OneTimePad::encrypt(b"padpad", b"secret")?;
+    ///     println!("Encrypted Data: {:?}", encrypted_literal);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt(
+        pad_buffer: impl AsRef<[u8]>,
+        plain_text_buffer: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>, OtpError> {
+        operate(pad_buffer.as_ref(), plain_text_buffer.as_ref())
+    }
+
+    /// Returns an error if the buffers are empty or their lengths differ.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let decrypted_data = OneTimePad::decrypt(&pad, &vec![1,2,3,4,5,6])?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decrypt(
+        pad_buffer: impl AsRef<[u8]>,
+        encrypted_data_buffer: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>, OtpError> {
+        operate(pad_buffer.as_ref(), encrypted_data_buffer.as_ref())
+    }
+
+    /// Like `decrypt`, but the plain text is zeroed when the returned buffer
+    /// is dropped instead of being left behind in freed memory.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let decrypted_data = OneTimePad::decrypt_secret(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Decrypted Data: {:?}", &decrypted_data[..]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "zeroize")]
+    pub fn decrypt_secret(
+        pad_buffer: &[u8],
+        encrypted_data_buffer: &[u8],
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>, OtpError> {
+        operate(pad_buffer, encrypted_data_buffer).map(zeroize::Zeroizing::new)
+    }
+
+    /// Encrypts the UTF-8 bytes of `plain_text`. The pad must be as long as
+    /// the text in bytes, not characters.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad("héllo".len()).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_str(&pad, "héllo")?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_str(pad_buffer: &[u8], plain_text: &str) -> Result<Vec<u8>, OtpError> {
+        operate(pad_buffer, plain_text.as_bytes())
+    }
+
+    /// Decrypts data produced by `encrypt_str` back into a `String`. Returns
+    /// `InvalidUtf8` if the result is not valid UTF-8, which usually means the
+    /// wrong pad was used. The error's `utf8_error().valid_up_to()` gives the
+    /// byte index where the text stops being valid.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(5).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_str(&pad, "hello")?;
+    ///     let decrypted_text = OneTimePad::decrypt_to_string(&pad, &encrypted_data)?;
+    ///     println!("Decrypted Text: {}", decrypted_text);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_to_string(pad_buffer: &[u8], encrypted_data_buffer: &[u8]) -> Result<String, OtpError> {
+        String::from_utf8(operate(pad_buffer, encrypted_data_buffer)?).map_err(OtpError::InvalidUtf8)
+    }
+
+    /// Encrypts by adding each pad byte to the data byte mod 256 instead of
+    /// XORing, for compatibility with systems that use additive pads. Unlike
+    /// XOR this is not its own inverse: decrypt with `decrypt_sub`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_add(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_add(pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        combine(pad_buffer, plain_text_buffer, |data, pad| data.wrapping_add(pad))
+    }
+
+    /// Reverses `encrypt_add` by subtracting each pad byte mod 256.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let decrypted_data = OneTimePad::decrypt_sub(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_sub(pad_buffer: &[u8], encrypted_data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        combine(pad_buffer, encrypted_data_buffer, |data, pad| data.wrapping_sub(pad))
+    }
+
+    /// Encrypts with a pad that is no longer needed afterwards, zeroing the
+    /// pad's memory (including spare capacity) before it is freed. The pad is
+    /// wiped even if encryption fails.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = vec![7, 6, 5, 4, 3, 2];
+    ///     let encrypted_data = OneTimePad::encrypt_and_zeroize(pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "zeroize")]
+    pub fn encrypt_and_zeroize(mut pad_buffer: Vec<u8>, plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        use zeroize::Zeroize;
+
+        let result = operate(&pad_buffer, plain_text_buffer);
+        pad_buffer.zeroize();
+        result
+    }
+
+    /// Encrypts against `pad_buffer[offset..offset + plain_text_buffer.len()]`,
+    /// letting several messages be carved out of one long pad. Fails with
+    /// `OutOfPad` if the region runs past the end of the pad, even where
+    /// `offset + len` would overflow.
+    ///
+    /// Nothing stops two calls from using overlapping regions, which reuses
+    /// pad bytes. Use `PadState` to have regions handed out without overlap.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(12).unwrap();
+    ///     let first = OneTimePad::encrypt_with_offset(&pad, &[1, 2, 3, 4, 5, 6], 0)?;
+    ///     let second = OneTimePad::encrypt_with_offset(&pad, &[1, 2, 3, 4, 5, 6], 6)?;
+    ///     println!("Encrypted Data: {:?} {:?}", first, second);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_with_offset(
+        pad_buffer: &[u8],
+        plain_text_buffer: &[u8],
+        offset: usize,
+    ) -> Result<Vec<u8>, OtpError> {
+        operate(pad_segment(pad_buffer, offset, plain_text_buffer.len())?, plain_text_buffer)
+    }
+
+    /// Decrypts against `pad_buffer[offset..offset + encrypted_data_buffer.len()]`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(12).unwrap();
+    ///     let decrypted_data = OneTimePad::decrypt_with_offset(&pad, &[1, 2, 3, 4, 5, 6], 6)?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_with_offset(
+        pad_buffer: &[u8],
+        encrypted_data_buffer: &[u8],
+        offset: usize,
+    ) -> Result<Vec<u8>, OtpError> {
+        operate(pad_segment(pad_buffer, offset, encrypted_data_buffer.len())?, encrypted_data_buffer)
+    }
+
+    /// Encrypts with the first `plain_text_buffer.len()` bytes of a pad that
+    /// may be longer than the data, returning the ciphertext and the number
+    /// of pad bytes used. Fails with `OutOfPad` if the pad is shorter than the
+    /// data. Unlike `encrypt`, a longer pad is not an error, so only use this
+    /// when the rest of the pad is deliberately kept for later messages.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(1024).unwrap();
+    ///     let (encrypted_data, used) = OneTimePad::encrypt_with_prefix(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     let (decrypted_data, _) = OneTimePad::decrypt_with_prefix(&pad, &encrypted_data)?;
+    ///     println!("Used {} pad bytes for {:?}", used, decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_with_prefix(pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<(Vec<u8>, usize), OtpError> {
+        let encrypted_data = Self::encrypt_with_offset(pad_buffer, plain_text_buffer, 0)?;
+        Ok((encrypted_data, plain_text_buffer.len()))
+    }
+
+    /// Reverses `encrypt_with_prefix`.
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_with_prefix(
+        pad_buffer: &[u8],
+        encrypted_data_buffer: &[u8],
+    ) -> Result<(Vec<u8>, usize), OtpError> {
+        let decrypted_data = Self::decrypt_with_offset(pad_buffer, encrypted_data_buffer, 0)?;
+        Ok((decrypted_data, encrypted_data_buffer.len()))
+    }
+
+    /// Encrypts against the pad starting at `*cursor`, then moves the cursor
+    /// past the bytes used so the next message can't reuse them. On error the
+    /// cursor is left where it was.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(12).unwrap();
+    ///     let mut cursor = 0;
+    ///     for message in [[1, 2, 3, 4, 5, 6], [6, 5, 4, 3, 2, 1]].iter() {
+    ///         let encrypted_data = OneTimePad::encrypt_advancing(&pad, message, &mut cursor)?;
+    ///         println!("Encrypted Data: {:?}", encrypted_data);
+    ///     }
+    ///     assert_eq!(cursor, 12);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_advancing(
+        pad_buffer: &[u8],
+        plain_text_buffer: &[u8],
+        cursor: &mut usize,
+    ) -> Result<Vec<u8>, OtpError> {
+        let encrypted_data = Self::encrypt_with_offset(pad_buffer, plain_text_buffer, *cursor)?;
+        *cursor += plain_text_buffer.len();
+        Ok(encrypted_data)
+    }
+
+    /// Decrypts against the pad starting at `*cursor` and moves the cursor on.
+    /// See `encrypt_advancing`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(12).unwrap();
+    ///     let mut cursor = 6;
+    ///     let decrypted_data = OneTimePad::decrypt_advancing(&pad, &[1, 2, 3, 4, 5, 6], &mut cursor)?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_advancing(
+        pad_buffer: &[u8],
+        encrypted_data_buffer: &[u8],
+        cursor: &mut usize,
+    ) -> Result<Vec<u8>, OtpError> {
+        let decrypted_data = Self::decrypt_with_offset(pad_buffer, encrypted_data_buffer, *cursor)?;
+        *cursor += encrypted_data_buffer.len();
+        Ok(decrypted_data)
+    }
+
+    /// Encrypts under several independently delivered pads at once, as if
+    /// `encrypt` had been applied with each in turn. The order of the pads
+    /// doesn't matter. Every pad must be as long as the data, and the result
+    /// is allocated once rather than once per pad.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let first = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let second = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_layers(&[&first, &second], &[1, 2, 3, 4, 5, 6])?;
+    ///     let decrypted_data = OneTimePad::decrypt_layers(&[&second, &first], &encrypted_data)?;
+    ///     assert_eq!(decrypted_data, vec![1, 2, 3, 4, 5, 6]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_layers(pad_buffers: &[&[u8]], plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        operate_layers(pad_buffers, plain_text_buffer)
+    }
+
+    /// Reverses `encrypt_layers`; the pads can be given in any order.
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_layers(pad_buffers: &[&[u8]], encrypted_data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        operate_layers(pad_buffers, encrypted_data_buffer)
+    }
+
+    /// Encrypts data that arrives in several pieces, such as a header and a
+    /// body, as if they had been concatenated first, without the extra copy.
+    /// The pieces together must be exactly as long as the pad.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let (header, body) = (vec![1, 2], vec![3, 4, 5, 6]);
+    ///     let encrypted_data = OneTimePad::encrypt_vectored(&pad, &[&header, &body])?;
+    ///     assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, [1, 2, 3, 4, 5, 6])?);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_vectored(pad_buffer: &[u8], plain_text_parts: &[&[u8]]) -> Result<Vec<u8>, OtpError> {
+        let data_len: usize = plain_text_parts.iter().map(|part| part.len()).sum();
+        if pad_buffer.is_empty() || data_len == 0 {
+            return Err(OtpError::EmptyBuffer);
+        }
+        if pad_buffer.len() != data_len {
+            return Err(OtpError::LengthMismatch {
+                pad_len: pad_buffer.len(),
+                data_len,
+            });
+        }
+
+        let mut result = Vec::with_capacity(data_len);
+        for part in plain_text_parts {
+            let start = result.len();
+            result.extend_from_slice(part);
+            xor(&pad_buffer[start..], &mut result[start..]);
+        }
+
+        Ok(result)
+    }
+
+    /// Writes the encrypted data into the front of `output_buffer` and returns
+    /// the number of bytes written. Nothing is allocated.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut output = [0u8; 16];
+    ///     let written = OneTimePad::encrypt_into(&pad, &[1, 2, 3, 4, 5, 6], &mut output)?;
+    ///     println!("Encrypted Data: {:?}", &output[..written]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_into(
+        pad_buffer: &[u8],
+        plain_text_buffer: &[u8],
+        output_buffer: &mut [u8],
+    ) -> Result<usize, OtpError> {
+        error_check(pad_buffer, plain_text_buffer)?;
+
+        let needed = plain_text_buffer.len();
+        let available = output_buffer.len();
+        let output = output_buffer
+            .get_mut(..needed)
+            .ok_or(OtpError::OutputTooSmall { needed, available })?;
+
+        for ((out, &pad), &data) in output.iter_mut().zip(pad_buffer).zip(plain_text_buffer) {
+            *out = pad ^ data;
+        }
+
+        Ok(needed)
+    }
+
+    /// Writes the decrypted data into the front of `output_buffer` and returns
+    /// the number of bytes written. Nothing is allocated.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut output = [0u8; 6];
+    ///     OneTimePad::decrypt_into(&pad, &[1, 2, 3, 4, 5, 6], &mut output)?;
+    ///     println!("Decrypted Data: {:?}", output);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_into(
+        pad_buffer: &[u8],
+        encrypted_data_buffer: &[u8],
+        output_buffer: &mut [u8],
+    ) -> Result<usize, OtpError> {
+        Self::encrypt_into(pad_buffer, encrypted_data_buffer, output_buffer)
+    }
+
+    /// Writes `pad_buffer[i] ^ data_buffer[i]` into `output_buffer[i]`. All
+    /// three buffers must be the same, non-zero length. Nothing is allocated.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut output = [0u8; 6];
+    ///     OneTimePad::xor_into(&pad, &[1, 2, 3, 4, 5, 6], &mut output)?;
+    ///     println!("Encrypted Data: {:?}", output);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn xor_into(pad_buffer: &[u8], data_buffer: &[u8], output_buffer: &mut [u8]) -> Result<(), OtpError> {
+        if output_buffer.len() != data_buffer.len() {
+            error_check(pad_buffer, data_buffer)?;
+            return Err(OtpError::OutputLengthMismatch {
+                data_len: data_buffer.len(),
+                output_len: output_buffer.len(),
+            });
+        }
+        Self::encrypt_into(pad_buffer, data_buffer, output_buffer)?;
+        Ok(())
+    }
+
+    /// Encrypts `data_buffer` in place. See `xor_in_place`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut data = [1, 2, 3, 4, 5, 6];
+    ///     OneTimePad::encrypt_in_place(&pad, &mut data)?;
+    ///     println!("Encrypted Data: {:?}", data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_in_place(pad_buffer: &[u8], plain_text_buffer: &mut [u8]) -> Result<(), OtpError> {
+        Self::xor_in_place(pad_buffer, plain_text_buffer)
+    }
+
+    /// Decrypts `encrypted_data_buffer` in place. See `xor_in_place`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut data = [1, 2, 3, 4, 5, 6];
+    ///     OneTimePad::decrypt_in_place(&pad, &mut data)?;
+    ///     println!("Decrypted Data: {:?}", data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_in_place(pad_buffer: &[u8], encrypted_data_buffer: &mut [u8]) -> Result<(), OtpError> {
+        Self::xor_in_place(pad_buffer, encrypted_data_buffer)
+    }
+
+    /// XORs the pad directly into `data_buffer`, avoiding the allocation made
+    /// by `encrypt`/`decrypt`. The buffer is left untouched on error.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut data = [1, 2, 3, 4, 5, 6];
+    ///     OneTimePad::xor_in_place(&pad, &mut data)?;
+    ///     println!("Encrypted Data: {:?}", data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn xor_in_place(pad_buffer: &[u8], data_buffer: &mut [u8]) -> Result<(), OtpError> {
+        error_check(pad_buffer, data_buffer)?;
+        xor(pad_buffer, data_buffer);
+        Ok(())
+    }
+
+    /// XORs two fixed-size arrays into a new array on the stack, for
+    /// fixed-format records such as 16 or 32 byte tokens. The lengths are
+    /// checked at compile time and nothing is allocated.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    ///
+    /// fn main() {
+    ///     let mut pad = [0u8; 16];
+    ///     OneTimePad::generate_random_pad_into(&mut pad).unwrap();
+    ///     let encrypted_data = OneTimePad::xor_array(&pad, b"sixteen byte tok");
+    ///     assert_eq!(&OneTimePad::xor_array(&pad, &encrypted_data), b"sixteen byte tok");
+    /// }
+    /// ```
+    pub fn xor_array<const N: usize>(pad_buffer: &[u8; N], data_buffer: &[u8; N]) -> [u8; N] {
+        let mut result = *data_buffer;
+        xor(pad_buffer, &mut result);
+        result
+    }
+
+    /// XORs a pad and data of any `Copy` element type with `^`, such as
+    /// `u16` or `u32` samples. Lengths are checked like `encrypt`, counted
+    /// in elements rather than bytes.
+    ///
+    /// `encrypt` and `decrypt` don't go through this: bytes take a faster
+    /// word at a time path that a generic element type can't.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad: [u16; 3] = [0x0f0f, 0xffff, 0x1234];
+    ///     let encrypted_data = OneTimePad::xor_typed(&pad, &[1, 2, 3])?;
+    ///     assert_eq!(OneTimePad::xor_typed(&pad, &encrypted_data)?, vec![1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn xor_typed<T: Copy + BitXor<Output = T>>(pad_buffer: &[T], data_buffer: &[T]) -> Result<Vec<T>, OtpError> {
+        error_check(pad_buffer, data_buffer)?;
+        Ok(xor_slices(pad_buffer, data_buffer))
+    }
+
+    /// Lazily XORs two byte iterators, for data that arrives piece by piece.
+    ///
+    /// Unlike `encrypt` the lengths are not checked: the iterator stops as
+    /// soon as either the pad or the data runs out, so a short pad silently
+    /// truncates the output. Compare the number of bytes produced with the
+    /// data length if that matters.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    ///
+    /// fn main() {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data: Vec<u8> =
+    ///         OneTimePad::xor_iter(pad.iter().copied(), vec![1, 2, 3, 4, 5, 6]).collect();
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    /// }
+    /// ```
+    pub fn xor_iter<P, D>(pad: P, data: D) -> XorIter<P::IntoIter, D::IntoIter>
+    where
+        P: IntoIterator<Item = u8>,
+        D: IntoIterator<Item = u8>,
+    {
+        XorIter::new(pad.into_iter(), data.into_iter())
+    }
+
+    /// Like `xor_iter`, but yields an `IterLengthMismatch` error instead of
+    /// stopping quietly if the pad and data have different lengths.
+    ///
+    /// ```rust
+    /// use one_time_pad::{OneTimePad, OtpError};
+    ///
+    /// fn main() {
+    ///     let pad = OneTimePad::generate_random_pad(5).unwrap();
+    ///     let encrypted_data: Result<Vec<u8>, OtpError> =
+    ///         OneTimePad::xor_iter_strict(pad.iter().copied(), vec![1, 2, 3, 4, 5, 6]).collect();
+    ///     assert!(encrypted_data.is_err());
+    /// }
+    /// ```
+    pub fn xor_iter_strict<P, D>(pad: P, data: D) -> StrictXorIter<P::IntoIter, D::IntoIter>
+    where
+        P: IntoIterator<Item = u8>,
+        D: IntoIterator<Item = u8>,
+    {
+        StrictXorIter::new(pad.into_iter(), data.into_iter())
+    }
+
+    /// **For teaching only.** XORs two ciphertexts of the same length. If
+    /// both were encrypted with the same pad, the pad cancels out and the
+    /// result is the XOR of the two plaintexts, which is usually enough to
+    /// recover both. This demonstrates the two-time pad attack; it has no use
+    /// in protecting real data.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(5).unwrap();
+    ///     let first = OneTimePad::encrypt(&pad, b"hello")?;
+    ///     let second = OneTimePad::encrypt(&pad, b"world")?;
+    ///
+    ///     // Reusing the pad leaks the plaintexts' XOR. Knowing one reveals the other.
+    ///     let leaked = OneTimePad::xor_ciphertexts(&first, &second)?;
+    ///     assert_eq!(OneTimePad::xor_ciphertexts(&leaked, b"hello")?, b"world");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn xor_ciphertexts(first_buffer: &[u8], second_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        operate(first_buffer, second_buffer)
+    }
+
+    /// **Not a one-time pad.** XORs `data_buffer` with `key_buffer` repeated
+    /// as often as needed, for obfuscation or checksums where secrecy does
+    /// not matter. A repeating key is easily broken, so never use this to
+    /// protect real data. Fails with `EmptyBuffer` if the key is empty.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let obfuscated = OneTimePad::xor_repeating(&[0x5a, 0xa5], &[1, 2, 3, 4, 5, 6])?;
+    ///     assert_eq!(OneTimePad::xor_repeating(&[0x5a, 0xa5], &obfuscated)?, vec![1, 2, 3, 4, 5, 6]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn xor_repeating(key_buffer: &[u8], data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        if key_buffer.is_empty() {
+            return Err(OtpError::EmptyBuffer);
+        }
+
+        Ok(key_buffer.iter().cycle().zip(data_buffer).map(|(key, data)| key ^ data).collect())
+    }
+
+    /// Same result as `encrypt`, but every pad and data byte is read with a
+    /// volatile load so the optimizer cannot specialise the loop on byte
+    /// values. The only branches depend on the buffer lengths, never on their
+    /// contents.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::xor_ct(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn xor_ct(pad_buffer: &[u8], data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        error_check(pad_buffer, data_buffer)?;
+
+        let mut result = vec![0u8; data_buffer.len()];
+        for ((out, pad), data) in result.iter_mut().zip(pad_buffer).zip(data_buffer) {
+            // Safe: both pointers come from live shared references.
+            *out = unsafe { ptr::read_volatile(pad) ^ ptr::read_volatile(data) };
+        }
+
+        Ok(result)
+    }
+
+    /// Compares two secrets, such as decrypted data or pad fingerprints, in
+    /// time that depends only on their lengths and not on where they differ.
+    /// Buffers of different lengths are never equal.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    ///
+    /// fn main() {
+    ///     let decrypted_data = [1, 2, 3, 4, 5, 6];
+    ///     assert!(OneTimePad::ct_eq(&decrypted_data, &[1, 2, 3, 4, 5, 6]));
+    ///     assert!(!OneTimePad::ct_eq(&decrypted_data, &[1, 2, 3, 4, 5, 7]));
+    /// }
+    /// ```
+    pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+
+        let mut difference = 0u8;
+        for (x, y) in a.iter().zip(b) {
+            // Safe: both pointers come from live shared references.
+            difference |= unsafe { ptr::read_volatile(x) ^ ptr::read_volatile(y) };
+        }
+        // Only the final accumulated value is branched on.
+        unsafe { ptr::read_volatile(&difference) == 0 }
+    }
+
+    /// Fails with `ZeroLength` if `length` is zero, since an empty pad can't
+    /// encrypt anything.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     println!("Encryption Pad: {:?}", pad); 
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_random_pad(length: usize) -> Result<Pad, OtpError> {
+        if length == 0 {
+            return Err(OtpError::ZeroLength);
+        }
+
+        // Fill the spare capacity directly rather than zeroing a buffer only
+        // to overwrite it, which is noticeable for very large pads.
+        let mut pad = Vec::with_capacity(length);
+        getrandom::getrandom_uninit(&mut pad.spare_capacity_mut()[..length])?;
+        // Safety: `getrandom_uninit` succeeded, so the first `length` bytes
+        // are initialized.
+        unsafe { pad.set_len(length) };
+        Ok(Pad::from(pad))
+    }
+
+    /// Generates a fresh pad exactly as long as `plain_text_buffer` and
+    /// encrypts with it, returning `(pad, ciphertext)`. Send the two over
+    /// separate channels. Fails with `EmptyBuffer` if there is nothing to
+    /// encrypt.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let (pad, encrypted_data) = OneTimePad::encrypt_fresh(&[1, 2, 3, 4, 5, 6])?;
+    ///     assert_eq!(OneTimePad::decrypt(&pad, &encrypted_data)?, vec![1, 2, 3, 4, 5, 6]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_fresh(plain_text_buffer: &[u8]) -> Result<(Pad, Vec<u8>), OtpError> {
+        if plain_text_buffer.is_empty() {
+            return Err(OtpError::EmptyBuffer);
+        }
+
+        let pad = Self::generate_random_pad(plain_text_buffer.len())?;
+        let encrypted_data = operate(&pad, plain_text_buffer)?;
+        Ok((pad, encrypted_data))
+    }
+
+    /// Encrypts like `encrypt` and also returns a fresh random 16 byte id for
+    /// tagging the ciphertext, e.g. as a database key recording which pad
+    /// bytes it used. The id is not secret and plays no part in the
+    /// encryption.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let (encrypted_data, id) = OneTimePad::encrypt_with_id(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Stored {:?} under {:02x?}", encrypted_data, id);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_with_id(pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<(Vec<u8>, [u8; 16]), OtpError> {
+        let encrypted_data = operate(pad_buffer, plain_text_buffer)?;
+        let mut id = [0u8; 16];
+        Self::generate_random_pad_into(&mut id)?;
+        Ok((encrypted_data, id))
+    }
+
+    /// Fills an existing buffer with random pad bytes instead of allocating.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() {
+    ///     let mut pad = [0u8; 6];
+    ///     OneTimePad::generate_random_pad_into(&mut pad).unwrap();
+    ///     println!("Encryption Pad: {:?}", pad);
+    /// }
+    /// ```
+    pub fn generate_random_pad_into(pad_buffer: &mut [u8]) -> Result<(), OtpError> {
+        Ok(getrandom::getrandom(pad_buffer)?)
+    }
+
+    /// Builds a pad of `length` bytes from a caller-supplied source, such as a
+    /// hardware RNG or a seeded generator for reproducible tests. Fails with
+    /// `ZeroLength` if `length` is zero.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_pad_with(6, |buffer| {
+    ///         for (i, byte) in buffer.iter_mut().enumerate() {
+    ///             *byte = i as u8;
+    ///         }
+    ///     })?;
+    ///     println!("Encryption Pad: {:?}", pad.as_slice());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_pad_with<F: FnMut(&mut [u8])>(length: usize, mut fill: F) -> Result<Pad, OtpError> {
+        if length == 0 {
+            return Err(OtpError::ZeroLength);
+        }
+
+        let mut pad = Pad::from(vec![0; length]);
+        fill(pad.as_mut_slice());
+        Ok(pad)
+    }
+
+    /// Builds a pad of `length` bytes from any cryptographically secure
+    /// `rand_core` generator. The `CryptoRng` bound keeps fast but predictable
+    /// generators such as `SmallRng` from being used by accident. Fails with
+    /// `ZeroLength` if `length` is zero.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use rand_chacha::rand_core::SeedableRng;
+    /// use rand_chacha::ChaCha20Rng;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let mut seed = [0u8; 32];
+    ///     OneTimePad::generate_random_pad_into(&mut seed)?;
+    ///     let mut rng = ChaCha20Rng::from_seed(seed);
+    ///     let pad = OneTimePad::generate_pad_with_rng(&mut rng, 6)?;
+    ///     println!("Encryption Pad: {:?}", pad);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn generate_pad_with_rng<R: rand_core::RngCore + rand_core::CryptoRng>(
+        rng: &mut R,
+        length: usize,
+    ) -> Result<Pad, OtpError> {
+        Self::generate_pad_with(length, |buffer| rng.fill_bytes(buffer))
+    }
+
+    /// **Insecure: for tests only.** Expands `seed` into a pad with ChaCha20,
+    /// so the same seed always gives the same pad. Anyone who knows or
+    /// guesses the seed can decrypt everything encrypted with the pad.
+    ///
+    /// Only available with the `insecure-test-rng` feature, which should only
+    /// ever be enabled for dev-dependencies.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::insecure_generate_pad_from_seed([42; 32], 6);
+    ///     let encrypted_data = OneTimePad::encrypt(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Reproducible Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "insecure-test-rng")]
+    pub fn insecure_generate_pad_from_seed(seed: [u8; 32], length: usize) -> Pad {
+        use rand_chacha::rand_core::{RngCore, SeedableRng};
+
+        let mut pad = Pad::from(vec![0; length]);
+        rand_chacha::ChaCha20Rng::from_seed(seed).fill_bytes(pad.as_mut_slice());
+        pad
+    }
+
+    /// Encodes pad material as lowercase hex for copying between machines.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     println!("Encryption Pad: {}", OneTimePad::pad_to_hex(&pad));
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn pad_to_hex(pad_buffer: &[u8]) -> String {
+        hex::encode(pad_buffer)
+    }
+
+    /// Parses pad material from hex. See `Pad::from_hex`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::pad_from_hex("070605040302")?;
+    ///     let encrypted_data = OneTimePad::encrypt(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn pad_from_hex(hex: &str) -> Result<Pad, OtpError> {
+        Pad::from_hex(hex)
+    }
+
+    /// Decrypts hex ciphertext with a hex pad, for pasting both from a
+    /// terminal. The pad is decoded first, so if both strings are malformed
+    /// the error describes the pad. Decoded lengths must match, as with
+    /// `decrypt`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let decrypted_data = OneTimePad::decrypt_hex("070605040302", "060406000604")?;
+    ///     assert_eq!(decrypted_data, vec![1, 2, 3, 4, 5, 6]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_hex(pad_hex: &str, cipher_hex: &str) -> Result<Vec<u8>, OtpError> {
+        let pad = Pad::from_hex(pad_hex)?;
+        let encrypted_data = hex::decode(cipher_hex)?;
+        operate(&pad, &encrypted_data)
+    }
+
+    /// XORs any number of equal-length pads together. Used with `split_pad`
+    /// to rebuild a pad from all of its shares.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let combined = OneTimePad::combine_pads(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]])?;
+    ///     println!("Combined Pad: {:?}", combined.as_slice());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn combine_pads(pads: &[&[u8]]) -> Result<Pad, OtpError> {
+        let (first, rest) = pads.split_first().ok_or(OtpError::EmptyBuffer)?;
+        if first.is_empty() {
+            return Err(OtpError::EmptyBuffer);
+        }
+
+        let mut combined = Pad::from(first.to_vec());
+        for pad_buffer in rest {
+            error_check(pad_buffer, &combined)?;
+            xor(pad_buffer, combined.as_mut_slice());
+        }
+
+        Ok(combined)
+    }
+
+    /// Splits a pad into `shares` pads that must all be combined with
+    /// `combine_pads` to get it back. All but the last share are random; the
+    /// last is the pad XORed with the others.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let shares = OneTimePad::split_pad(&pad, 3)?;
+    ///     let share_slices: Vec<&[u8]> = shares.iter().map(|share| share.as_slice()).collect();
+    ///     assert_eq!(OneTimePad::combine_pads(&share_slices)?.as_slice(), pad.as_slice());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn split_pad(pad_buffer: &[u8], shares: usize) -> Result<Vec<Pad>, OtpError> {
+        if pad_buffer.is_empty() {
+            return Err(OtpError::EmptyBuffer);
+        }
+        if shares == 0 {
+            return Err(OtpError::TooFewShares { shares, minimum: 1 });
+        }
+
+        let mut last = Pad::from(pad_buffer.to_vec());
+        let mut result = Vec::with_capacity(shares);
+        for _ in 1..shares {
+            let share = Self::generate_random_pad(pad_buffer.len())?;
+            xor(&share, last.as_mut_slice());
+            result.push(share);
+        }
+        result.push(last);
+
+        Ok(result)
+    }
+
+    /// Cuts a master pad into consecutive, non-overlapping slices of
+    /// `chunk_len` bytes, the last possibly shorter, so each device can be
+    /// given its own segment. Chunk `i` starts at pad byte `i * chunk_len`.
+    ///
+    /// Never give two devices the same chunk or overlapping ranges: any pad
+    /// byte used twice breaks the one-time pad. The chunks carry no labels,
+    /// so keep a record of which chunk index went to which device. Fails with
+    /// `EmptyBuffer` for an empty pad and `ZeroLength` if `chunk_len` is
+    /// zero.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(10).unwrap();
+    ///     let chunks = OneTimePad::partition_pad(&pad, 4)?;
+    ///     assert_eq!(chunks.len(), 3);
+    ///     assert_eq!(chunks[2], &pad[8..]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn partition_pad(pad_buffer: &[u8], chunk_len: usize) -> Result<Vec<&[u8]>, OtpError> {
+        if pad_buffer.is_empty() {
+            return Err(OtpError::EmptyBuffer);
+        }
+        if chunk_len == 0 {
+            return Err(OtpError::ZeroLength);
+        }
+
+        Ok(pad_buffer.chunks(chunk_len).collect())
+    }
+
+    /// Like `partition_pad`, but copies each chunk into its own `Pad`, which
+    /// is wiped when dropped.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(10).unwrap();
+    ///     let chunks = OneTimePad::partition_pad_owned(&pad, 4)?;
+    ///     assert_eq!(chunks[0].as_slice(), &pad[..4]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn partition_pad_owned(pad_buffer: &[u8], chunk_len: usize) -> Result<Vec<Pad>, OtpError> {
+        let chunks = Self::partition_pad(pad_buffer, chunk_len)?;
+        Ok(chunks.into_iter().map(|chunk| Pad::from(chunk.to_vec())).collect())
+    }
+
+    /// n-of-n secret sharing: splits `secret` into `shares` shares that
+    /// reveal nothing about it unless every one of them is combined with
+    /// `combine_shares`. Like `split_pad`, but fails with `TooFewShares` for
+    /// fewer than two shares, since a single share is the secret itself.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let shares = OneTimePad::split_secret(b"attack at dawn", 3)?;
+    ///     let share_slices: Vec<&[u8]> = shares.iter().map(|share| share.as_slice()).collect();
+    ///     assert_eq!(OneTimePad::combine_shares(&share_slices)?.as_slice(), b"attack at dawn");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn split_secret(secret: &[u8], shares: usize) -> Result<Vec<Pad>, OtpError> {
+        if shares < 2 {
+            return Err(OtpError::TooFewShares { shares, minimum: 2 });
+        }
+        Self::split_pad(secret, shares)
+    }
+
+    /// Recovers the secret from all the shares made by `split_secret`. The
+    /// shares must all be the same length. The secret is returned as a `Pad`
+    /// so it is wiped when dropped.
+    #[cfg(feature = "alloc")]
+    pub fn combine_shares(shares: &[&[u8]]) -> Result<Pad, OtpError> {
+        if shares.len() < 2 {
+            return Err(OtpError::TooFewShares {
+                shares: shares.len(),
+                minimum: 2,
+            });
+        }
+        Self::combine_pads(shares)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn operate(pad_buffer: &[u8], data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+    let mut result = data_buffer.to_vec();
+    OneTimePad::xor_in_place(pad_buffer, &mut result)?;
+
+    Ok(result)
+}
+
+/// Element-wise XOR over the overlapping length of `a` and `b`.
+#[cfg(feature = "alloc")]
+fn xor_slices<T: Copy + BitXor<Output = T>>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter().zip(b).map(|(&a, &b)| a ^ b).collect()
+}
+
+#[cfg(feature = "alloc")]
+fn operate_layers(pad_buffers: &[&[u8]], data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+    if pad_buffers.is_empty() {
+        return Err(OtpError::EmptyBuffer);
+    }
+    // Check every pad before allocating, so a bad layer costs nothing.
+    for pad_buffer in pad_buffers {
+        error_check(pad_buffer, data_buffer)?;
+    }
+
+    let mut result = data_buffer.to_vec();
+    for pad_buffer in pad_buffers {
+        xor(pad_buffer, &mut result);
+    }
+
+    Ok(result)
+}
+
+#[cfg(feature = "alloc")]
+fn combine<F: Fn(u8, u8) -> u8>(pad_buffer: &[u8], data_buffer: &[u8], f: F) -> Result<Vec<u8>, OtpError> {
+    error_check(pad_buffer, data_buffer)?;
+
+    Ok(data_buffer.iter().zip(pad_buffer).map(|(&data, &pad)| f(data, pad)).collect())
+}
+
+#[cfg(not(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"))))]
+fn xor(pad_buffer: &[u8], data_buffer: &mut [u8]) {
+    xor_scalar(pad_buffer, data_buffer)
+}
+
+fn xor_scalar(pad_buffer: &[u8], data_buffer: &mut [u8]) {
+    // Like `zip`, only the overlapping length is processed.
+    let length = pad_buffer.len().min(data_buffer.len());
+    let (pad_buffer, data_buffer) = (&pad_buffer[..length], &mut data_buffer[..length]);
+
+    // XOR a word at a time for the bulk of the buffer, then finish the tail
+    // byte by byte. Loading through byte arrays means neither buffer has to
+    // be aligned.
+    let mut data_words = data_buffer.chunks_exact_mut(WORD_SIZE);
+    let mut pad_words = pad_buffer.chunks_exact(WORD_SIZE);
+    for (data, pad) in (&mut data_words).zip(&mut pad_words) {
+        let word = u64::from_ne_bytes(data.try_into().unwrap()) ^ u64::from_ne_bytes(pad.try_into().unwrap());
+        data.copy_from_slice(&word.to_ne_bytes());
+    }
+
+    for (data, &pad) in data_words.into_remainder().iter_mut().zip(pad_words.remainder()) {
+        *data ^= pad;
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn pad_segment(pad_buffer: &[u8], offset: usize, length: usize) -> Result<&[u8], OtpError> {
+    offset
+        .checked_add(length)
+        .and_then(|end| pad_buffer.get(offset..end))
+        .ok_or(OtpError::OutOfPad {
+            offset,
+            needed: length,
+            available: pad_buffer.len().saturating_sub(offset),
+        })
+}
+
+fn error_check<T>(pad_buffer: &[T], data_buffer: &[T]) -> Result<(), OtpError> {
+    if pad_buffer.is_empty() || data_buffer.is_empty() {
+        return Err(OtpError::EmptyBuffer);
+    }
+    if pad_buffer.len() != data_buffer.len() {
+        return Err(OtpError::LengthMismatch {
+            pad_len: pad_buffer.len(),
+            data_len: data_buffer.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+
+    use crate::{EntropySource, OneTimePad, OtpError, SourceError};
+
+    fn generate_random_data(length: usize) -> Result<Vec<u8>, getrandom::Error> {
+        let mut arr: Vec<u8> = vec![0; length];
+        getrandom::getrandom(&mut arr)?;
+        // let vec: Vec<i16> = arr.iter().map(|x| *x as i16).collect();
+        Ok(arr)
+    }
+
+    #[test]
+    fn can_load_random_values_into_buffer() {
+        let empty = [0u8; 64];
+
+        let res = OneTimePad::generate_random_pad(64);
+        let result = res.unwrap();
+
+        assert_ne!(result.as_slice(), empty);
+    }
+
+    #[test]
+    fn large_pad_is_fully_filled() {
+        let length = (4 << 20) + 3;
+
+        let pad = OneTimePad::generate_random_pad(length).unwrap();
+
+        assert_eq!(pad.len(), length);
+        // Every 4 KiB stretch of a random pad contains a non-zero byte, so an
+        // unfilled stretch would show up here.
+        assert!(pad.chunks_exact(4096).all(|chunk| chunk.iter().any(|&byte| byte != 0)));
+    }
+
+    #[test]
+    fn rng_failures_convert_to_otp_error() {
+        let error = OtpError::from(getrandom::Error::UNSUPPORTED);
+
+        assert!(matches!(error, OtpError::Rng(e) if e == getrandom::Error::UNSUPPORTED));
+    }
+
+    #[test]
+    fn rng_failures_surface_through_pad_generation() {
+        struct BrokenRng;
+
+        impl EntropySource for BrokenRng {
+            fn fill(&mut self, _buffer: &mut [u8]) -> Result<(), SourceError> {
+                Err(SourceError::from(getrandom::Error::UNSUPPORTED))
+            }
+        }
+
+        let result = OneTimePad::generate_pad_from_source(&mut BrokenRng, 16);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::Rng(e)) if e == getrandom::Error::UNSUPPORTED
+        ));
+    }
+
+    #[test]
+    fn zero_length_pad_is_rejected() {
+        let res = OneTimePad::generate_random_pad(0);
+
+        assert!(matches!(res, Err(OtpError::ZeroLength)));
+    }
+
+    #[test]
+    fn encrypt_fresh_pad_matches_data_length() {
+        for length in [1, 7, 4096].iter() {
+            let plain_text = generate_random_data(*length).unwrap();
+
+            let (pad, encrypted_data) = OneTimePad::encrypt_fresh(&plain_text).unwrap();
+
+            assert_eq!((pad.len(), encrypted_data.len()), (*length, *length));
+            assert_eq!(OneTimePad::decrypt(&pad, &encrypted_data).unwrap(), plain_text);
+        }
+        assert!(matches!(OneTimePad::encrypt_fresh(&[]), Err(OtpError::EmptyBuffer)));
+    }
+
+    #[test]
+    fn encrypt_with_id_tags_each_ciphertext() {
+        let pad = [7, 6, 5, 4];
+
+        let (first, first_id) = OneTimePad::encrypt_with_id(&pad, &[1, 2, 3, 4]).unwrap();
+        let (second, second_id) = OneTimePad::encrypt_with_id(&pad, &[1, 2, 3, 4]).unwrap();
+
+        assert_eq!(first, OneTimePad::encrypt(pad, [1, 2, 3, 4]).unwrap());
+        assert_eq!(first, second);
+        assert_ne!(first_id, second_id);
+        assert!(matches!(OneTimePad::encrypt_with_id(&pad, &[1]), Err(OtpError::LengthMismatch { .. })));
+    }
+
+    #[test]
+    fn encrypt_not_empty() {
+        let plain_text = vec![1, 2, 3, 4, 5, 6, 7];
+        let pad = vec![7, 6, 5, 4, 3, 2, 1];
+
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+
+        assert_ne!(encrypted_data.len(), 0);
+    }
+
+    #[test]
+    fn basic_encrypt_then_decrypt() {
+        let plain_text = vec![1, 2, 3, 4, 5, 6, 7];
+        let pad = vec![7, 6, 5, 4, 3, 2, 1];
+
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data).unwrap();
+
+        assert_ne!(plain_text, encrypted_data);
+        assert_ne!(encrypted_data, decrypted_data);
+
+        assert_eq!(plain_text, decrypted_data);
+    }
+
+    #[test]
+    fn edge_cases_encrypt() {
+        let plain_text = vec![0, 0, 0, 1, 1, 1, 255, 255, 255];
+        let pad = vec![0, 1, 255, 0, 1, 255, 0, 1, 255];
+
+        let known_result: Vec<u8> = vec![0, 1, 255, 1, 0, 254, 255, 254, 0];
+
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+
+        assert_eq!(known_result, encrypted_data);
+    }
+
+    #[test]
+    fn small_randomised_encrypt_then_decrypt() {
+        let plain_text = generate_random_data(10).unwrap();
+        let pad = OneTimePad::generate_random_pad(10).unwrap();
+
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data).unwrap();
+
+        assert_ne!(plain_text, encrypted_data);
+        assert_ne!(encrypted_data, decrypted_data);
+
+        assert_eq!(plain_text, decrypted_data);
+    }
+
+    #[test]
+    fn large_randomised_encrypt_then_decrypt() {
+        let plain_text = generate_random_data(1000).unwrap();
+        let pad = OneTimePad::generate_random_pad(1000).unwrap();
+
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data).unwrap();
+
+        assert_ne!(plain_text, encrypted_data);
+        assert_ne!(encrypted_data, decrypted_data);
+
+        assert_eq!(plain_text, decrypted_data);
+    }
+
+    #[test]
+    fn huge_randomised_encrypt_then_decrypt() {
+        let plain_text = generate_random_data(100000).unwrap();
+        let pad = OneTimePad::generate_random_pad(100000).unwrap();
+
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data).unwrap();
+
+        assert_ne!(plain_text, encrypted_data);
+        assert_ne!(encrypted_data, decrypted_data);
+
+        assert_eq!(plain_text, decrypted_data);
+    }
+
+    #[test]
+    fn different_pad_results_in_same_plain_text_when_decrypting() {
+        let plain_text = generate_random_data(10).unwrap();
+
+        let pad = OneTimePad::generate_random_pad(10).unwrap();
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data).unwrap();
+
+        let new_pad = OneTimePad::generate_random_pad(10).unwrap();
+        let new_encrypted_data = OneTimePad::encrypt(&new_pad, &plain_text).unwrap();
+        let new_decrypted_data = OneTimePad::decrypt(&new_pad, &new_encrypted_data).unwrap();
+
+        assert_eq!(decrypted_data, new_decrypted_data);
+    }
+
+    #[test]
+    fn large_different_pad_results_in_same_plain_text_when_decrypting() {
+        let plain_text = generate_random_data(1000).unwrap();
+
+        let pad = OneTimePad::generate_random_pad(1000).unwrap();
+        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data).unwrap();
+
+        let new_pad = OneTimePad::generate_random_pad(1000).unwrap();
+        let new_encrypted_data = OneTimePad::encrypt(&new_pad, &plain_text).unwrap();
+        let new_decrypted_data = OneTimePad::decrypt(&new_pad, &new_encrypted_data).unwrap();
+
+        assert_eq!(decrypted_data, new_decrypted_data);
+    }
+
+    #[test]
+    fn encrypt_rejects_pad_longer_than_data() {
+        let result = OneTimePad::encrypt([1, 2, 3, 4], [1, 2, 3]);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::LengthMismatch { pad_len: 4, data_len: 3 })
+        ));
+    }
+
+    #[test]
+    fn decrypt_rejects_data_longer_than_pad() {
+        let result = OneTimePad::decrypt([1, 2, 3], [1, 2, 3, 4]);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::LengthMismatch { pad_len: 3, data_len: 4 })
+        ));
+    }
+
+    #[test]
+    fn encrypt_rejects_empty_buffers() {
+        assert!(matches!(OneTimePad::encrypt([], []), Err(OtpError::EmptyBuffer)));
+        assert!(matches!(OneTimePad::encrypt([], [1]), Err(OtpError::EmptyBuffer)));
+        assert!(matches!(OneTimePad::decrypt([1], []), Err(OtpError::EmptyBuffer)));
+    }
+
+    #[test]
+    fn mismatch_error_describes_lengths() {
+        let error = OneTimePad::encrypt([1, 2], [1, 2, 3]).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "pad length (2) does not match data length (3)"
+        );
+    }
+
+    #[test]
+    fn accepts_arrays_sub_slices_and_boxed_slices() {
+        let buffer = [9u8, 1, 2, 3, 4, 9];
+        let pad: Box<[u8]> = vec![7, 6, 5, 4].into_boxed_slice();
+
+        let from_slice = OneTimePad::encrypt(&pad, &buffer[1..5]).unwrap();
+        let from_array = OneTimePad::encrypt(&pad, [1, 2, 3, 4]).unwrap();
+
+        assert_eq!(from_slice, vec![6, 4, 6, 0]);
+        assert_eq!(from_slice, from_array);
+    }
+
+    #[test]
+    fn accepts_owned_and_borrowed_byte_containers() {
+        let pad = vec![7u8, 6, 5, 4];
+        let expected = vec![6, 4, 6, 0];
 
-impl OneTimePad {
+        assert_eq!(OneTimePad::encrypt([7, 6, 5, 4], [1, 2, 3, 4]).unwrap(), expected);
+        assert_eq!(OneTimePad::encrypt(&pad[..], &[1u8, 2, 3, 4][..]).unwrap(), expected);
+        assert_eq!(OneTimePad::encrypt(pad.clone(), vec![1, 2, 3, 4]).unwrap(), expected);
+        assert_eq!(OneTimePad::encrypt(&pad, String::from("\u{1}\u{2}\u{3}\u{4}").as_bytes()).unwrap(), expected);
 
-    /// ```rust
-    /// use one_time_pad::OneTimePad;
-    /// use std::error::Error;
-    ///
-    /// fn main() {
-    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
-    ///     let encrypted_data = OneTimePad::encrypt(&pad, &vec![1,2,3,4,5,6]);
-    ///     println!("Encrypted Data: {:?}", encrypted_data); 
-    /// }
-    /// ```
-    pub fn encrypt(pad_buffer: &Vec<u8>, plain_text_buffer: &Vec<u8>) -> Vec<u8> {
-        return operate(pad_buffer, plain_text_buffer);
+        let encrypted_literal = OneTimePad::encrypt(b"padpad", b"secret").unwrap();
+        assert_eq!(OneTimePad::decrypt(b"padpad", encrypted_literal).unwrap(), b"secret");
     }
 
-    /// ```rust
-    /// use one_time_pad::OneTimePad;
-    /// use std::error::Error;
-    ///
-    /// fn main() {
-    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
-    ///     let encrypted_data = OneTimePad::decrypt(&pad, &vec![1,2,3,4,5,6]);
-    ///     println!("Encrypted Data: {:?}", encrypted_data); 
-    /// }
-    /// ```
-    pub fn decrypt(pad_buffer: &Vec<u8>, encrypted_data_buffer: &Vec<u8>) -> Vec<u8> {
-        return operate(pad_buffer, encrypted_data_buffer);
+    #[test]
+    fn xor_in_place_round_trips() {
+        let plain_text = generate_random_data(100).unwrap();
+        let pad = OneTimePad::generate_random_pad(100).unwrap();
+
+        let mut buffer = plain_text.clone();
+        OneTimePad::xor_in_place(&pad, &mut buffer).unwrap();
+        assert_eq!(buffer, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+
+        OneTimePad::xor_in_place(&pad, &mut buffer).unwrap();
+        assert_eq!(buffer, plain_text);
     }
 
-    /// ```rust
-    /// use one_time_pad::OneTimePad;
-    /// use std::error::Error;
-    ///
-    /// fn main() {
-    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
-    ///     println!("Encryption Pad: {:?}", pad); 
-    /// }
-    /// ```
-    pub fn generate_random_pad(length: usize) -> Result<Vec<u8>, getrandom::Error> {
-        let mut arr: Vec<u8> = vec![0; length];
-        getrandom::getrandom(&mut arr)?;
-        // let vec: Vec<i16> = arr.iter().map(|x| *x as i16).collect();
-        Ok(arr)
+    #[test]
+    fn xor_in_place_rejects_mismatched_lengths() {
+        let mut buffer = [1, 2, 3];
+
+        let result = OneTimePad::xor_in_place(&[1, 2], &mut buffer);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::LengthMismatch { pad_len: 2, data_len: 3 })
+        ));
     }
 
-    // pub fn build_pad_from_file(pad_buffer: &Vec<u8>, length: usize) -> Vec<u8> {
+    #[test]
+    fn in_place_matches_allocating_encrypt() {
+        let plain_text = generate_random_data(1000).unwrap();
+        let pad = OneTimePad::generate_random_pad(1000).unwrap();
+
+        let mut buffer = plain_text.clone();
+        OneTimePad::encrypt_in_place(&pad, &mut buffer).unwrap();
 
-    // }
-}
+        assert_eq!(buffer, OneTimePad::encrypt(&pad, &plain_text).unwrap());
 
-fn operate(pad_buffer: &Vec<u8>, data_buffer: &Vec<u8>) -> Vec<u8> {
-    error_check(&pad_buffer, &data_buffer);
+        OneTimePad::decrypt_in_place(&pad, &mut buffer).unwrap();
 
-    let result: Vec<u8> = pad_buffer
-        .iter()
-        .zip(data_buffer.iter())
-        .map(|(&x1, &x2)| x1 ^ x2)
-        .collect();
+        assert_eq!(buffer, plain_text);
+    }
 
-    return result;
-}
+    #[test]
+    fn in_place_leaves_buffer_untouched_on_mismatch() {
+        let plain_text = vec![1, 2, 3, 4, 5];
+        let pad = vec![9, 9, 9, 9];
+
+        let mut buffer = plain_text.clone();
 
-fn error_check(buf_a: &Vec<u8>, buf_b: &Vec<u8>) {
-    if buf_a.len() != buf_b.len() || buf_a.len() == 0 || buf_b.len() == 0 {
-        panic!("Buffer lengths do not match or cannot be zero");
+        assert!(OneTimePad::encrypt_in_place(&pad, &mut buffer).is_err());
+        assert_eq!(buffer, plain_text);
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn offset_encryption_uses_the_requested_pad_segment() {
+        let pad = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let plain_text = vec![0, 0, 0];
 
-    use crate::OneTimePad;
+        let encrypted_data = OneTimePad::encrypt_with_offset(&pad, &plain_text, 5).unwrap();
+        assert_eq!(encrypted_data, vec![6, 7, 8]);
 
-    fn generate_random_data(length: usize) -> Result<Vec<u8>, getrandom::Error> {
-        let mut arr: Vec<u8> = vec![0; length];
-        getrandom::getrandom(&mut arr)?;
-        // let vec: Vec<i16> = arr.iter().map(|x| *x as i16).collect();
-        Ok(arr)
+        let decrypted_data = OneTimePad::decrypt_with_offset(&pad, &encrypted_data, 5).unwrap();
+        assert_eq!(decrypted_data, plain_text);
     }
 
     #[test]
-    fn can_load_random_values_into_buffer() {
-        let empty = [0u8; 64];
+    fn adjacent_offsets_are_independent_but_overlaps_leak() {
+        let pad = OneTimePad::generate_random_pad(16).unwrap();
+        let (first, second) = (b"attack a", b"retreat!");
 
-        let res = OneTimePad::generate_random_pad(64);
-        let result = res.unwrap();
+        let first_data = OneTimePad::encrypt_with_offset(&pad, first, 0).unwrap();
+        let second_data = OneTimePad::encrypt_with_offset(&pad, second, 8).unwrap();
+        assert_eq!(OneTimePad::decrypt_with_offset(&pad, &first_data, 0).unwrap(), first);
+        assert_eq!(OneTimePad::decrypt_with_offset(&pad, &second_data, 8).unwrap(), second);
 
-        assert_ne!(result, empty);
+        // Asking for an overlapping region reuses pad bytes, and the shared
+        // part cancels out.
+        let overlapping = OneTimePad::encrypt_with_offset(&pad, second, 4).unwrap();
+        let leaked = OneTimePad::xor_ciphertexts(&first_data[4..], &overlapping[..4]).unwrap();
+        assert_eq!(leaked, OneTimePad::encrypt(&first[4..], &second[..4]).unwrap());
     }
 
     #[test]
-    fn encrypt_not_empty() {
-        let plain_text = vec![1, 2, 3, 4, 5, 6, 7];
-        let pad = vec![7, 6, 5, 4, 3, 2, 1];
+    fn prefix_encryption_reports_pad_used() {
+        let pad = vec![1, 2, 3, 4, 5, 6, 7, 8];
 
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
+        let (longer, used) = OneTimePad::encrypt_with_prefix(&pad, &[0, 0, 0]).unwrap();
+        assert_eq!((longer, used), (vec![1, 2, 3], 3));
 
-        assert_ne!(encrypted_data.len(), 0);
+        let (equal, used) = OneTimePad::encrypt_with_prefix(&pad, &[0; 8]).unwrap();
+        assert_eq!((equal, used), (pad.clone(), 8));
+
+        let result = OneTimePad::encrypt_with_prefix(&pad, &[0; 9]);
+        assert!(matches!(
+            result,
+            Err(OtpError::OutOfPad { offset: 0, needed: 9, available: 8 })
+        ));
+        assert!(matches!(OneTimePad::encrypt(&pad, [0, 0, 0]), Err(OtpError::LengthMismatch { .. })));
+        assert_eq!(OneTimePad::decrypt_with_prefix(&pad, &[1, 2]).unwrap(), (vec![0, 0], 2));
     }
 
     #[test]
-    fn basic_encrypt_then_decrypt() {
-        let plain_text = vec![1, 2, 3, 4, 5, 6, 7];
-        let pad = vec![7, 6, 5, 4, 3, 2, 1];
+    fn offset_encryption_rejects_running_past_the_pad() {
+        let pad = vec![1, 2, 3, 4, 5, 6, 7, 8];
 
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
-        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data);
+        let result = OneTimePad::encrypt_with_offset(&pad, &[0, 0, 0], 6);
+        assert!(matches!(
+            result,
+            Err(OtpError::OutOfPad { offset: 6, needed: 3, available: 2 })
+        ));
 
-        assert_ne!(plain_text, encrypted_data);
-        assert_ne!(encrypted_data, decrypted_data);
+        let result = OneTimePad::encrypt_with_offset(&pad, &[0], usize::MAX);
+        assert!(matches!(
+            result,
+            Err(OtpError::OutOfPad { available: 0, .. })
+        ));
+    }
 
-        assert_eq!(plain_text, decrypted_data);
+    #[test]
+    fn advancing_encryption_never_reuses_pad_bytes() {
+        let pad = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let mut sender = 0;
+        let mut receiver = 0;
+
+        let first = OneTimePad::encrypt_advancing(&pad, &[0, 0, 0], &mut sender).unwrap();
+        let second = OneTimePad::encrypt_advancing(&pad, &[0, 0, 0], &mut sender).unwrap();
+        assert_eq!((first.clone(), second.clone(), sender), (vec![1, 2, 3], vec![4, 5, 6], 6));
+
+        let result = OneTimePad::encrypt_advancing(&pad, &[0, 0, 0], &mut sender);
+        assert!(matches!(
+            result,
+            Err(OtpError::OutOfPad { offset: 6, needed: 3, available: 2 })
+        ));
+        assert_eq!(sender, 6);
+
+        assert_eq!(OneTimePad::decrypt_advancing(&pad, &first, &mut receiver).unwrap(), vec![0, 0, 0]);
+        assert_eq!(OneTimePad::decrypt_advancing(&pad, &second, &mut receiver).unwrap(), vec![0, 0, 0]);
+        assert_eq!(receiver, 6);
     }
 
     #[test]
-    fn edge_cases_encrypt() {
-        let plain_text = vec![0, 0, 0, 1, 1, 1, 255, 255, 255];
-        let pad = vec![0, 1, 255, 0, 1, 255, 0, 1, 255];
+    fn layered_encryption_needs_every_pad_in_any_order() {
+        let plain_text = generate_random_data(64).unwrap();
+        let pads: Vec<_> = (0..3).map(|_| OneTimePad::generate_random_pad(64).unwrap()).collect();
+        let (a, b, c) = (pads[0].as_slice(), pads[1].as_slice(), pads[2].as_slice());
 
-        let known_result: Vec<u8> = vec![0, 1, 255, 1, 0, 254, 255, 254, 0];
+        let encrypted_data = OneTimePad::encrypt_layers(&[a, b, c], &plain_text).unwrap();
+        let mut one_at_a_time = plain_text.clone();
+        for pad in [b, a, c].iter() {
+            one_at_a_time = OneTimePad::encrypt(pad, &one_at_a_time).unwrap();
+        }
 
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
+        assert_eq!(encrypted_data, one_at_a_time);
+        assert_eq!(OneTimePad::decrypt_layers(&[c, a, b], &encrypted_data).unwrap(), plain_text);
+        for layers in [[a, b], [a, c], [b, c]].iter() {
+            assert_ne!(OneTimePad::decrypt_layers(layers, &encrypted_data).unwrap(), plain_text);
+        }
+    }
 
-        assert_eq!(known_result, encrypted_data);
+    #[test]
+    fn vectored_encryption_matches_concatenated_encrypt() {
+        let plain_text = generate_random_data(100).unwrap();
+        let pad = OneTimePad::generate_random_pad(100).unwrap();
+        let expected = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+
+        let parts: [&[u8]; 5] = [&plain_text[..3], &[], &plain_text[3..64], &plain_text[64..99], &plain_text[99..]];
+        assert_eq!(OneTimePad::encrypt_vectored(&pad, &parts).unwrap(), expected);
+        assert_eq!(OneTimePad::encrypt_vectored(&pad, &[&plain_text]).unwrap(), expected);
+
+        assert!(matches!(
+            OneTimePad::encrypt_vectored(&pad, &parts[..4]),
+            Err(OtpError::LengthMismatch { pad_len: 100, data_len: 99 })
+        ));
+        assert!(matches!(OneTimePad::encrypt_vectored(&pad, &[]), Err(OtpError::EmptyBuffer)));
     }
 
     #[test]
-    fn small_randomised_encrypt_then_decrypt() {
-        let plain_text = generate_random_data(10).unwrap();
-        let pad = OneTimePad::generate_random_pad(10).unwrap();
+    fn layered_encryption_rejects_bad_pads() {
+        assert!(matches!(OneTimePad::encrypt_layers(&[], &[1, 2, 3]), Err(OtpError::EmptyBuffer)));
+        assert!(matches!(
+            OneTimePad::encrypt_layers(&[&[1, 2, 3], &[1, 2]], &[1, 2, 3]),
+            Err(OtpError::LengthMismatch { pad_len: 2, data_len: 3 })
+        ));
+        assert!(matches!(OneTimePad::decrypt_layers(&[&[1]], &[]), Err(OtpError::EmptyBuffer)));
+    }
 
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
-        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data);
+    #[test]
+    fn encrypt_into_matches_allocating_encrypt() {
+        let plain_text = generate_random_data(100).unwrap();
+        let pad = OneTimePad::generate_random_pad(100).unwrap();
 
-        assert_ne!(plain_text, encrypted_data);
-        assert_ne!(encrypted_data, decrypted_data);
+        let mut output = vec![0; 128];
+        let written = OneTimePad::encrypt_into(&pad, &plain_text, &mut output).unwrap();
 
-        assert_eq!(plain_text, decrypted_data);
+        assert_eq!(written, 100);
+        assert_eq!(output[..written], OneTimePad::encrypt(&pad, &plain_text).unwrap()[..]);
+        assert_eq!(output[written..], [0; 28]);
+
+        let mut decrypted_data = vec![0; 100];
+        OneTimePad::decrypt_into(&pad, &output[..written], &mut decrypted_data).unwrap();
+
+        assert_eq!(decrypted_data, plain_text);
     }
 
     #[test]
-    fn large_randomised_encrypt_then_decrypt() {
+    fn encrypt_into_rejects_small_output() {
+        let mut output = [0; 2];
+
+        let result = OneTimePad::encrypt_into(&[1, 2, 3], &[4, 5, 6], &mut output);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::OutputTooSmall { needed: 3, available: 2 })
+        ));
+    }
+
+    #[test]
+    fn can_fill_existing_buffer_with_random_values() {
+        let empty = [0u8; 64];
+        let mut pad = [0u8; 64];
+
+        OneTimePad::generate_random_pad_into(&mut pad).unwrap();
+
+        assert_ne!(pad, empty);
+    }
+
+    #[test]
+    fn constant_time_xor_matches_encrypt() {
         let plain_text = generate_random_data(1000).unwrap();
         let pad = OneTimePad::generate_random_pad(1000).unwrap();
 
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
-        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data);
+        let encrypted_data = OneTimePad::xor_ct(&pad, &plain_text).unwrap();
 
-        assert_ne!(plain_text, encrypted_data);
-        assert_ne!(encrypted_data, decrypted_data);
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+        assert_eq!(OneTimePad::xor_ct(&pad, &encrypted_data).unwrap(), plain_text);
+        assert!(OneTimePad::xor_ct(&pad, &plain_text[1..]).is_err());
+    }
 
-        assert_eq!(plain_text, decrypted_data);
+    #[test]
+    fn reused_pad_cancels_out_of_xored_ciphertexts() {
+        let pad = OneTimePad::generate_random_pad(14).unwrap();
+        let first = OneTimePad::encrypt(&pad, b"attack at dawn").unwrap();
+        let second = OneTimePad::encrypt(&pad, b"retreat at ten").unwrap();
+
+        let leaked = OneTimePad::xor_ciphertexts(&first, &second).unwrap();
+
+        let plain_texts_xored = OneTimePad::encrypt(b"attack at dawn", b"retreat at ten").unwrap();
+        assert_eq!(leaked, plain_texts_xored);
+        assert!(matches!(
+            OneTimePad::xor_ciphertexts(&first, &second[1..]),
+            Err(OtpError::LengthMismatch { .. })
+        ));
     }
 
     #[test]
-    fn huge_randomised_encrypt_then_decrypt() {
-        let plain_text = generate_random_data(100000).unwrap();
-        let pad = OneTimePad::generate_random_pad(100000).unwrap();
+    fn xor_repeating_cycles_the_key() {
+        let encrypted_data = OneTimePad::xor_repeating(&[1, 2, 3], &[0; 7]).unwrap();
 
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
-        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data);
+        assert_eq!(encrypted_data, vec![1, 2, 3, 1, 2, 3, 1]);
+        assert_eq!(OneTimePad::xor_repeating(&[1, 2, 3], &[0; 2]).unwrap(), vec![1, 2]);
+        assert_eq!(OneTimePad::xor_repeating(&[1], &[]).unwrap(), Vec::<u8>::new());
+        assert!(matches!(OneTimePad::xor_repeating(&[], &[1]), Err(OtpError::EmptyBuffer)));
+    }
 
-        assert_ne!(plain_text, encrypted_data);
-        assert_ne!(encrypted_data, decrypted_data);
+    #[test]
+    fn ct_eq_compares_contents_and_lengths() {
+        let secret = generate_random_data(64).unwrap();
+        assert!(OneTimePad::ct_eq(&secret, &secret.clone()));
+        assert!(OneTimePad::ct_eq(&[], &[]));
 
-        assert_eq!(plain_text, decrypted_data);
+        let mut first = secret.clone();
+        first[0] ^= 0x80;
+        assert!(!OneTimePad::ct_eq(&secret, &first));
+
+        let mut last = secret.clone();
+        last[63] ^= 0x01;
+        assert!(!OneTimePad::ct_eq(&secret, &last));
+
+        assert!(!OneTimePad::ct_eq(&secret, &secret[..63]));
+        assert!(!OneTimePad::ct_eq(&[], &[0]));
     }
 
+    #[cfg(feature = "zeroize")]
     #[test]
-    fn different_pad_results_in_same_plain_text_when_decrypting() {
-        let plain_text = generate_random_data(10).unwrap();
+    fn decrypt_secret_matches_decrypt() {
+        let pad = OneTimePad::generate_random_pad(64).unwrap();
+        let encrypted_data = generate_random_data(64).unwrap();
 
-        let pad = OneTimePad::generate_random_pad(10).unwrap();
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
-        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data);
+        let secret: zeroize::Zeroizing<Vec<u8>> = OneTimePad::decrypt_secret(&pad, &encrypted_data).unwrap();
 
-        let new_pad = OneTimePad::generate_random_pad(10).unwrap();
-        let new_encrypted_data = OneTimePad::encrypt(&new_pad, &plain_text);
-        let new_decrypted_data = OneTimePad::decrypt(&new_pad, &new_encrypted_data);
+        assert_eq!(*secret, OneTimePad::decrypt(&pad, &encrypted_data).unwrap());
+        assert!(OneTimePad::decrypt_secret(&pad, &[]).is_err());
+    }
 
-        assert_eq!(decrypted_data, new_decrypted_data);
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn encrypt_and_zeroize_matches_encrypt() {
+        let plain_text = generate_random_data(100).unwrap();
+        let pad = OneTimePad::generate_random_pad(100).unwrap();
+
+        let encrypted_data = OneTimePad::encrypt_and_zeroize(pad.to_vec(), &plain_text).unwrap();
+
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
     }
 
     #[test]
-    fn large_different_pad_results_in_same_plain_text_when_decrypting() {
-        let plain_text = generate_random_data(1000).unwrap();
+    fn xor_into_requires_all_lengths_to_match() {
+        let mut output = [0; 3];
+        OneTimePad::xor_into(&[1, 2, 3], &[1, 1, 1], &mut output).unwrap();
+        assert_eq!(output, [0, 3, 2]);
 
-        let pad = OneTimePad::generate_random_pad(1000).unwrap();
-        let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
-        let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_data);
+        let mut long_output = [0; 4];
+        let result = OneTimePad::xor_into(&[1, 2, 3], &[1, 1, 1], &mut long_output);
+        assert!(matches!(
+            result,
+            Err(OtpError::OutputLengthMismatch { data_len: 3, output_len: 4 })
+        ));
 
-        let new_pad = OneTimePad::generate_random_pad(1000).unwrap();
-        let new_encrypted_data = OneTimePad::encrypt(&new_pad, &plain_text);
-        let new_decrypted_data = OneTimePad::decrypt(&new_pad, &new_encrypted_data);
+        let result = OneTimePad::xor_into(&[1, 2], &[1, 1, 1], &mut output);
+        assert!(matches!(result, Err(OtpError::LengthMismatch { .. })));
 
-        assert_eq!(decrypted_data, new_decrypted_data);
+        let result = OneTimePad::xor_into(&[], &[], &mut []);
+        assert!(matches!(result, Err(OtpError::EmptyBuffer)));
+    }
+
+    #[test]
+    fn pad_from_custom_source_gives_known_ciphertext() {
+        let mut state = 1u8;
+        let pad = OneTimePad::generate_pad_with(4, |buffer| {
+            for byte in buffer.iter_mut() {
+                state = state.wrapping_mul(5).wrapping_add(3);
+                *byte = state;
+            }
+        })
+        .unwrap();
+
+        assert_eq!(pad.as_slice(), &[8, 43, 218, 69]);
+        assert_eq!(OneTimePad::encrypt(&pad, [1, 2, 3, 4]).unwrap(), vec![9, 41, 217, 65]);
+    }
+
+    #[test]
+    fn custom_source_rejects_zero_length() {
+        let result = OneTimePad::generate_pad_with(0, |_| panic!("fill called for an empty pad"));
+
+        assert!(matches!(result, Err(OtpError::ZeroLength)));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn pad_from_seeded_rng_is_deterministic() {
+        use rand_chacha::rand_core::{RngCore, SeedableRng};
+        use rand_chacha::ChaCha20Rng;
+
+        let pad = OneTimePad::generate_pad_with_rng(&mut ChaCha20Rng::from_seed([7; 32]), 64).unwrap();
+        let same_seed = OneTimePad::generate_pad_with_rng(&mut ChaCha20Rng::from_seed([7; 32]), 64).unwrap();
+        let other_seed = OneTimePad::generate_pad_with_rng(&mut ChaCha20Rng::from_seed([8; 32]), 64).unwrap();
+
+        let mut expected = [0u8; 64];
+        ChaCha20Rng::from_seed([7; 32]).fill_bytes(&mut expected);
+
+        assert_eq!(pad.as_slice(), &expected[..]);
+        assert_eq!(pad.as_slice(), same_seed.as_slice());
+        assert_ne!(pad.as_slice(), other_seed.as_slice());
+        assert!(matches!(
+            OneTimePad::generate_pad_with_rng(&mut ChaCha20Rng::from_seed([7; 32]), 0),
+            Err(OtpError::ZeroLength)
+        ));
+    }
+
+    #[cfg(feature = "insecure-test-rng")]
+    #[test]
+    fn seeded_pad_matches_chacha20_keystream() {
+        // ChaCha20 keystream for an all-zero key and nonce (RFC 7539 A.1).
+        let pad = OneTimePad::insecure_generate_pad_from_seed([0; 32], 32);
+        assert_eq!(
+            pad.to_hex(),
+            "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7"
+        );
+
+        let long_pad = OneTimePad::insecure_generate_pad_from_seed([0; 32], 100);
+        assert_eq!(long_pad[..32], pad[..]);
+        assert_ne!(
+            OneTimePad::insecure_generate_pad_from_seed([1; 32], 32).as_slice(),
+            pad.as_slice()
+        );
+    }
+
+    #[test]
+    fn pad_hex_round_trip() {
+        let pad = OneTimePad::generate_random_pad(64).unwrap();
+
+        let hex = OneTimePad::pad_to_hex(&pad);
+
+        assert_eq!(hex.len(), 128);
+        assert_eq!(OneTimePad::pad_from_hex(&hex).unwrap().as_slice(), pad.as_slice());
+        assert!(matches!(
+            OneTimePad::pad_from_hex("0x00"),
+            Err(OtpError::InvalidHexCharacter { character: 'x', index: 1 })
+        ));
+    }
+
+    #[test]
+    fn xor_typed_matches_byte_encrypt_and_checks_lengths() {
+        let pad = OneTimePad::generate_random_pad(64).unwrap();
+        let data: Vec<u8> = (0..64).collect();
+
+        assert_eq!(
+            OneTimePad::xor_typed(&pad, &data).unwrap(),
+            OneTimePad::encrypt(&pad, &data).unwrap()
+        );
+
+        let wide_pad: Vec<u32> = pad.chunks(4).map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]])).collect();
+        let samples: Vec<u32> = (0..16).map(|sample| sample * 0x0101_0101).collect();
+        let encrypted_data = OneTimePad::xor_typed(&wide_pad, &samples).unwrap();
+        assert_eq!(OneTimePad::xor_typed(&wide_pad, &encrypted_data).unwrap(), samples);
+
+        assert!(matches!(
+            OneTimePad::xor_typed(&[1u16, 2, 3], &[1, 2]),
+            Err(OtpError::LengthMismatch { pad_len: 3, data_len: 2 })
+        ));
+        assert!(matches!(
+            OneTimePad::xor_typed::<u16>(&[], &[]),
+            Err(OtpError::EmptyBuffer)
+        ));
+    }
+
+    #[test]
+    fn decrypt_hex_decodes_both_inputs() {
+        let pad = OneTimePad::generate_random_pad(32).unwrap();
+        let encrypted_data = OneTimePad::encrypt(&pad, [9; 32]).unwrap();
+
+        let decrypted_data =
+            OneTimePad::decrypt_hex(&pad.to_hex(), &OneTimePad::pad_to_hex(&encrypted_data).to_uppercase()).unwrap();
+
+        assert_eq!(decrypted_data, vec![9; 32]);
+        assert!(matches!(
+            OneTimePad::decrypt_hex("0102", "01020"),
+            Err(OtpError::InvalidHexLength { length: 5 })
+        ));
+        assert!(matches!(
+            OneTimePad::decrypt_hex("0g", "zz"),
+            Err(OtpError::InvalidHexCharacter { character: 'g', index: 1 })
+        ));
+        assert!(matches!(
+            OneTimePad::decrypt_hex("0102", "01020304"),
+            Err(OtpError::LengthMismatch { pad_len: 2, data_len: 4 })
+        ));
+    }
+
+    #[test]
+    fn str_round_trip_with_multibyte_characters() {
+        let text = "Grüße, 世界! 🦀";
+        let pad = OneTimePad::generate_random_pad(text.len()).unwrap();
+
+        let encrypted_data = OneTimePad::encrypt_str(&pad, text).unwrap();
+
+        assert_eq!(encrypted_data.len(), text.len());
+        assert_eq!(OneTimePad::decrypt_to_string(&pad, &encrypted_data).unwrap(), text);
+        assert!(matches!(
+            OneTimePad::encrypt_str(&pad[..text.chars().count()], text),
+            Err(OtpError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn decrypt_to_string_rejects_invalid_utf8() {
+        let pad = [0x80, 0, 0];
+
+        let result = OneTimePad::decrypt_to_string(&pad, b"abc");
+
+        match result {
+            Err(OtpError::InvalidUtf8(e)) => assert_eq!(e.as_bytes(), &[0xe1, b'b', b'c']),
+            other => panic!("expected InvalidUtf8, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decrypt_to_string_with_wrong_pad_reports_where_text_breaks() {
+        let text = "東京の天気は晴れ 🌞🌞 ".repeat(4);
+        let pad = OneTimePad::generate_random_pad(text.len()).unwrap();
+        let wrong_pad = OneTimePad::generate_random_pad(text.len()).unwrap();
+        let encrypted_data = OneTimePad::encrypt_str(&pad, &text).unwrap();
+
+        // Random bytes of this length are practically never valid UTF-8.
+        match OneTimePad::decrypt_to_string(&wrong_pad, &encrypted_data) {
+            Err(OtpError::InvalidUtf8(e)) => {
+                let index = e.utf8_error().valid_up_to();
+                assert!(index < text.len());
+                assert!(e.to_string().contains(&format!("index {}", index)));
+            }
+            other => panic!("expected InvalidUtf8, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn add_mode_known_answers_at_wrap_boundary() {
+        let pad = [1, 1, 255, 0x80];
+        let plain_text = [255, 0, 1, 0x80];
+
+        let encrypted_data = OneTimePad::encrypt_add(&pad, &plain_text).unwrap();
+        assert_eq!(encrypted_data, vec![0, 1, 0, 0]);
+
+        let decrypted_data = OneTimePad::decrypt_sub(&pad, &[0, 0, 0, 0]).unwrap();
+        assert_eq!(decrypted_data, vec![255, 255, 1, 0x80]);
+        assert_eq!(OneTimePad::decrypt_sub(&pad, &encrypted_data).unwrap(), plain_text);
+    }
+
+    #[test]
+    fn add_mode_is_not_self_inverse() {
+        let plain_text = generate_random_data(256).unwrap();
+        let mut pad = OneTimePad::generate_random_pad(256).unwrap().as_slice().to_vec();
+        // Make sure at least one byte can't round trip through addition twice.
+        pad[0] = 1;
+
+        let encrypted_data = OneTimePad::encrypt_add(&pad, &plain_text).unwrap();
+
+        assert_eq!(OneTimePad::decrypt_sub(&pad, &encrypted_data).unwrap(), plain_text);
+        assert_ne!(OneTimePad::encrypt_add(&pad, &encrypted_data).unwrap(), plain_text);
+        assert_ne!(OneTimePad::decrypt(&pad, &encrypted_data).unwrap(), plain_text);
+        assert!(matches!(
+            OneTimePad::encrypt_add(&[1, 2], &[1, 2, 3]),
+            Err(OtpError::LengthMismatch { pad_len: 2, data_len: 3 })
+        ));
+    }
+
+    fn reference_xor(pad_buffer: &[u8], data_buffer: &[u8]) -> Vec<u8> {
+        data_buffer.iter().zip(pad_buffer).map(|(data, pad)| data ^ pad).collect()
+    }
+
+    #[test]
+    fn word_xor_matches_bytewise_for_every_alignment() {
+        let pad = generate_random_data(200).unwrap();
+        let data = generate_random_data(200).unwrap();
+
+        for length in 0..=64 {
+            for pad_offset in 0..8 {
+                for data_offset in 0..8 {
+                    let pad_slice = &pad[pad_offset..pad_offset + length];
+                    let mut result = data[data_offset..data_offset + length].to_vec();
+                    crate::xor(pad_slice, &mut result);
+
+                    assert_eq!(result, reference_xor(pad_slice, &data[data_offset..data_offset + length]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn word_xor_matches_bytewise_for_random_lengths() {
+        let mut lengths = [0u8; 32];
+        getrandom::getrandom(&mut lengths).unwrap();
+
+        for &length in &lengths {
+            let length = usize::from(length) * 41 + 1;
+            let pad = generate_random_data(length).unwrap();
+            let data = generate_random_data(length).unwrap();
+
+            let mut result = data.clone();
+            crate::xor(&pad, &mut result);
+
+            assert_eq!(result, reference_xor(&pad, &data));
+        }
+    }
+
+    #[test]
+    fn word_xor_only_touches_overlap() {
+        let pad = [0xff; 20];
+
+        let mut short_data = [0u8; 11];
+        crate::xor(&pad, &mut short_data);
+        assert_eq!(short_data, [0xff; 11]);
+
+        let mut long_data = [0u8; 20];
+        crate::xor(&pad[..13], &mut long_data);
+        assert_eq!(long_data[..13], [0xff; 13]);
+        assert_eq!(long_data[13..], [0; 7]);
+    }
+
+    #[test]
+    fn xor_array_matches_encrypt() {
+        let pad: [u8; 32] = core::array::from_fn(|index| index as u8 * 7);
+        let plain_text = [0x5a; 32];
+
+        let encrypted_data = OneTimePad::xor_array(&pad, &plain_text);
+
+        assert_eq!(encrypted_data.to_vec(), OneTimePad::encrypt(pad, plain_text).unwrap());
+        assert_eq!(OneTimePad::xor_array(&pad, &encrypted_data), plain_text);
+        assert_eq!(OneTimePad::xor_array::<0>(&[], &[]), [0u8; 0]);
+    }
+
+    #[test]
+    fn xor_iter_matches_encrypt() {
+        let plain_text = generate_random_data(100).unwrap();
+        let pad = OneTimePad::generate_random_pad(100).unwrap();
+
+        let encrypted_data: Vec<u8> =
+            OneTimePad::xor_iter(pad.iter().copied(), plain_text.iter().copied()).collect();
+
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+    }
+
+    #[test]
+    fn xor_iter_stops_at_shorter_input() {
+        let short_pad: Vec<u8> = OneTimePad::xor_iter(vec![1, 2], vec![0, 0, 0]).collect();
+        assert_eq!(short_pad, vec![1, 2]);
+
+        let short_data: Vec<u8> = OneTimePad::xor_iter(vec![1, 2, 3], vec![0]).collect();
+        assert_eq!(short_data, vec![1]);
+
+        // Works with unbounded sources as long as the other side ends.
+        let lazy: Vec<u8> = OneTimePad::xor_iter(core::iter::repeat(0xff), 0..4).collect();
+        assert_eq!(lazy, vec![0xff, 0xfe, 0xfd, 0xfc]);
+
+        let taken: Vec<u8> = OneTimePad::xor_iter(core::iter::repeat(1), core::iter::repeat(2))
+            .take(3)
+            .collect();
+        assert_eq!(taken, vec![3, 3, 3]);
+    }
+
+    #[test]
+    fn partition_pad_covers_pad_without_overlap() {
+        let pad: Vec<u8> = (0..10).collect();
+
+        let chunks = OneTimePad::partition_pad(&pad, 4).unwrap();
+
+        assert_eq!(chunks, vec![&[0, 1, 2, 3][..], &[4, 5, 6, 7], &[8, 9]]);
+        assert_eq!(chunks.concat(), pad);
+        assert_eq!(OneTimePad::partition_pad(&pad, 5).unwrap().len(), 2);
+        assert_eq!(OneTimePad::partition_pad(&pad, 20).unwrap(), vec![&pad[..]]);
+
+        let owned = OneTimePad::partition_pad_owned(&pad, 4).unwrap();
+        let owned: Vec<&[u8]> = owned.iter().map(|chunk| chunk.as_slice()).collect();
+        assert_eq!(owned, chunks);
+    }
+
+    #[test]
+    fn partition_pad_rejects_empty_pad_and_zero_chunks() {
+        assert!(matches!(OneTimePad::partition_pad(&[], 4), Err(OtpError::EmptyBuffer)));
+        assert!(matches!(OneTimePad::partition_pad(&[1, 2], 0), Err(OtpError::ZeroLength)));
+        assert!(matches!(OneTimePad::partition_pad_owned(&[1, 2], 0), Err(OtpError::ZeroLength)));
+    }
+
+    #[test]
+    fn split_pad_shares_combine_to_original() {
+        let pad = OneTimePad::generate_random_pad(64).unwrap();
+
+        for shares in 1..5 {
+            let split = OneTimePad::split_pad(&pad, shares).unwrap();
+            let share_slices: Vec<&[u8]> = split.iter().map(|share| share.as_slice()).collect();
+
+            assert_eq!(split.len(), shares);
+            assert_eq!(OneTimePad::combine_pads(&share_slices).unwrap().as_slice(), pad.as_slice());
+            if shares > 1 {
+                assert!(split.iter().all(|share| share.as_slice() != pad.as_slice()));
+                let missing_one = OneTimePad::combine_pads(&share_slices[1..]).unwrap();
+                assert_ne!(missing_one.as_slice(), pad.as_slice());
+            }
+        }
+    }
+
+    #[test]
+    fn secret_shares_round_trip_and_hide_the_secret() {
+        let secret = generate_random_data(48).unwrap();
+
+        for &count in [2, 3, 5].iter() {
+            let shares = OneTimePad::split_secret(&secret, count).unwrap();
+            let share_slices: Vec<&[u8]> = shares.iter().map(|share| share.as_slice()).collect();
+
+            assert_eq!(shares.len(), count);
+            assert_eq!(OneTimePad::combine_shares(&share_slices).unwrap().as_slice(), secret.as_slice());
+            // Leaving out any one share leaves random-looking bytes.
+            for missing in 0..count {
+                let mut partial = vec![0; secret.len()];
+                for (index, share) in shares.iter().enumerate().filter(|&(index, _)| index != missing) {
+                    assert_ne!(share.as_slice(), secret.as_slice(), "share {}", index);
+                    crate::xor(share, &mut partial);
+                }
+                assert_ne!(partial, secret, "without share {}", missing);
+            }
+        }
+    }
+
+    #[test]
+    fn secret_sharing_rejects_bad_shares() {
+        assert!(matches!(
+            OneTimePad::split_secret(&[1, 2, 3], 1),
+            Err(OtpError::TooFewShares { shares: 1, minimum: 2 })
+        ));
+        assert!(matches!(OneTimePad::split_secret(&[], 2), Err(OtpError::EmptyBuffer)));
+        assert!(matches!(
+            OneTimePad::combine_shares(&[&[1, 2, 3]]),
+            Err(OtpError::TooFewShares { shares: 1, minimum: 2 })
+        ));
+        assert!(matches!(
+            OneTimePad::combine_shares(&[&[1, 2, 3], &[4, 5, 6], &[7, 8]]),
+            Err(OtpError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn combine_pads_known_answer_and_errors() {
+        let combined = OneTimePad::combine_pads(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]]).unwrap();
+        assert_eq!(combined.as_slice(), &[2, 15, 12]);
+
+        assert!(matches!(OneTimePad::combine_pads(&[]), Err(OtpError::EmptyBuffer)));
+        assert!(matches!(OneTimePad::combine_pads(&[&[]]), Err(OtpError::EmptyBuffer)));
+        assert!(matches!(
+            OneTimePad::combine_pads(&[&[1, 2, 3], &[1, 2]]),
+            Err(OtpError::LengthMismatch { pad_len: 2, data_len: 3 })
+        ));
+        assert!(matches!(
+            OneTimePad::split_pad(&[1, 2, 3], 0),
+            Err(OtpError::TooFewShares { shares: 0, minimum: 1 })
+        ));
+        assert!(matches!(OneTimePad::split_pad(&[], 2), Err(OtpError::EmptyBuffer)));
     }
 }
@@ -0,0 +1,49 @@
+//! Keeping pad memory out of swap and core dumps. Locking is best effort:
+//! `lock` reports whether it worked rather than failing, since it is often
+//! refused by `RLIMIT_MEMLOCK` or unsupported.
+
+/// Locks `bytes` into RAM and, on Linux, excludes them from core dumps.
+/// Returns whether the lock succeeded.
+#[cfg(unix)]
+pub(crate) fn lock(bytes: &[u8]) -> bool {
+    let locked = unsafe { libc::mlock(bytes.as_ptr().cast(), bytes.len()) == 0 };
+
+    // `madvise` needs a page-aligned start, so the advice covers the whole
+    // pages the pad touches. It's a hint; failing it doesn't fail the lock.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        if page_size.is_power_of_two() {
+            let start = bytes.as_ptr() as usize & !(page_size - 1);
+            let length = bytes.as_ptr() as usize + bytes.len() - start;
+            unsafe { libc::madvise(start as *mut libc::c_void, length, libc::MADV_DONTDUMP) };
+        }
+    }
+
+    locked
+}
+
+/// Unlocks a range passed to `lock`, given as an address and length since
+/// the buffer may have been emptied since.
+#[cfg(unix)]
+pub(crate) fn unlock(address: usize, length: usize) {
+    unsafe { libc::munlock(address as *const libc::c_void, length) };
+}
+
+#[cfg(windows)]
+pub(crate) fn lock(bytes: &[u8]) -> bool {
+    unsafe { windows_sys::Win32::System::Memory::VirtualLock(bytes.as_ptr().cast(), bytes.len()) != 0 }
+}
+
+#[cfg(windows)]
+pub(crate) fn unlock(address: usize, length: usize) {
+    unsafe { windows_sys::Win32::System::Memory::VirtualUnlock(address as *const core::ffi::c_void, length) };
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn lock(_bytes: &[u8]) -> bool {
+    false
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn unlock(_address: usize, _length: usize) {}
@@ -0,0 +1,301 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::convert::TryInto;
+
+#[cfg(feature = "alloc")]
+use crate::OtpError;
+use crate::OneTimePad;
+
+/// Number of pad bytes consumed by one `compute_tag` key. Advance any pad
+/// offset or state by this much on top of the message length, and never use
+/// the same key bytes for two messages.
+pub const MAC_KEY_SIZE: usize = 32;
+/// Length of the tag produced by `compute_tag`.
+pub const MAC_TAG_SIZE: usize = 16;
+
+const BLOCK_SIZE: usize = 16;
+// x^128 + x^7 + x^2 + x + 1 in GCM's reflected bit order.
+const REDUCTION: u128 = 0xe1 << 120;
+
+impl OneTimePad {
+    /// Computes a one-time MAC over `message`, keyed with `MAC_KEY_SIZE`
+    /// (32) fresh pad bytes.
+    ///
+    /// The first 16 key bytes are the point `h` at which the message is
+    /// evaluated as a polynomial over GF(2^128), the last 16 mask the result.
+    /// This is GHASH from AES-GCM, so a tag matches GCM's when the key is
+    /// GCM's hash key and encrypted counter block. Unlike HMAC it is
+    /// unconditionally secure: a forger who sees one tag succeeds with
+    /// probability at most `(message.len() / 16 + 2) / 2^128`. That only holds
+    /// while each key is used for a single message.
+    ///
+    /// ```rust
+    /// use one_time_pad::{OneTimePad, MAC_KEY_SIZE};
+    /// use std::convert::TryInto;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let plain_text = [1, 2, 3, 4, 5, 6];
+    ///     let pad = OneTimePad::generate_random_pad(MAC_KEY_SIZE + plain_text.len())?;
+    ///     let (mac_key, pad) = pad.split_at(MAC_KEY_SIZE);
+    ///     let encrypted_data = OneTimePad::encrypt(pad, &plain_text)?;
+    ///     let tag = OneTimePad::compute_tag(mac_key.try_into()?, &encrypted_data);
+    ///     assert!(OneTimePad::verify_tag(mac_key.try_into()?, &encrypted_data, &tag));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn compute_tag(key_bytes: &[u8; MAC_KEY_SIZE], message: &[u8]) -> [u8; MAC_TAG_SIZE] {
+        let h = read_block(&key_bytes[..BLOCK_SIZE]);
+        let mask = read_block(&key_bytes[BLOCK_SIZE..]);
+
+        let mut hash = 0u128;
+        for block in message.chunks(BLOCK_SIZE) {
+            hash = gf_multiply(hash ^ read_block(block), h);
+        }
+        // Final block holds the bit lengths of the (empty) associated data
+        // and the message, so messages differing only in trailing zeroes
+        // get different tags.
+        hash = gf_multiply(hash ^ (message.len() as u128 * 8), h);
+
+        (hash ^ mask).to_be_bytes()
+    }
+
+    /// Checks a tag from `compute_tag` in constant time.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    ///
+    /// fn main() {
+    ///     let mac_key = [7; 32];
+    ///     let tag = OneTimePad::compute_tag(&mac_key, &[1, 2, 3, 4, 5, 6]);
+    ///     assert!(OneTimePad::verify_tag(&mac_key, &[1, 2, 3, 4, 5, 6], &tag));
+    ///     assert!(!OneTimePad::verify_tag(&mac_key, &[1, 2, 3, 4, 5, 7], &tag));
+    /// }
+    /// ```
+    pub fn verify_tag(key_bytes: &[u8; MAC_KEY_SIZE], message: &[u8], tag: &[u8]) -> bool {
+        OneTimePad::ct_eq(&OneTimePad::compute_tag(key_bytes, message), tag)
+    }
+
+    /// Encrypts `plain_text_buffer` and appends a one-time MAC tag over the
+    /// ciphertext, giving a result `MAC_TAG_SIZE` (16) bytes longer.
+    ///
+    /// `pad_buffer` must be exactly `MAC_KEY_SIZE + plain_text_buffer.len()`
+    /// bytes long. Its first `MAC_KEY_SIZE` (32) bytes key the MAC and the
+    /// rest is XORed with the plain text:
+    ///
+    /// ```text
+    /// pad:    [ MAC key (32) | XOR pad (n)    ]
+    /// sealed: [ ciphertext (n) | tag (16)     ]
+    /// ```
+    ///
+    /// A length mismatch reports the pad length against the plain text
+    /// length plus the MAC key.
+    ///
+    /// ```rust
+    /// use one_time_pad::{OneTimePad, MAC_KEY_SIZE};
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(MAC_KEY_SIZE + 6)?;
+    ///     let sealed = OneTimePad::seal(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     assert_eq!(OneTimePad::open(&pad, &sealed)?, vec![1, 2, 3, 4, 5, 6]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn seal(pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        let (mac_key, pad_buffer) = split_seal_pad(pad_buffer, plain_text_buffer.len())?;
+
+        let mut sealed = OneTimePad::encrypt(pad_buffer, plain_text_buffer)?;
+        let tag = OneTimePad::compute_tag(mac_key, &sealed);
+        sealed.extend_from_slice(&tag);
+        Ok(sealed)
+    }
+
+    /// Verifies and decrypts the output of `seal` using the same pad. Fails
+    /// with `TamperDetected`, without decrypting, if the tag is missing or
+    /// does not match, or if the sealed buffer has been truncated or
+    /// extended so that it no longer fits the pad.
+    ///
+    /// ```rust
+    /// use one_time_pad::{OneTimePad, MAC_KEY_SIZE};
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(MAC_KEY_SIZE + 6)?;
+    ///     let mut sealed = OneTimePad::seal(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     sealed[0] ^= 1;
+    ///     assert!(OneTimePad::open(&pad, &sealed).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn open(pad_buffer: &[u8], sealed_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        if sealed_buffer.len() < MAC_TAG_SIZE {
+            return Err(OtpError::TamperDetected);
+        }
+
+        let (encrypted_data, tag) = sealed_buffer.split_at(sealed_buffer.len() - MAC_TAG_SIZE);
+        // A pad that doesn't fit means the sealed buffer changed length.
+        let (mac_key, pad_buffer) =
+            split_seal_pad(pad_buffer, encrypted_data.len()).map_err(|_| OtpError::TamperDetected)?;
+        if !OneTimePad::verify_tag(mac_key, encrypted_data, tag) {
+            return Err(OtpError::TamperDetected);
+        }
+
+        OneTimePad::decrypt(pad_buffer, encrypted_data)
+    }
+}
+
+/// Splits a `seal` pad into the MAC key and the XOR pad for a message of
+/// `message_len` bytes.
+#[cfg(feature = "alloc")]
+fn split_seal_pad(pad_buffer: &[u8], message_len: usize) -> Result<(&[u8; MAC_KEY_SIZE], &[u8]), OtpError> {
+    if pad_buffer.len() != MAC_KEY_SIZE + message_len {
+        return Err(OtpError::LengthMismatch {
+            pad_len: pad_buffer.len(),
+            data_len: MAC_KEY_SIZE + message_len,
+        });
+    }
+
+    let (mac_key, pad_buffer) = pad_buffer.split_at(MAC_KEY_SIZE);
+    Ok((mac_key.try_into().unwrap(), pad_buffer))
+}
+
+/// Reads up to 16 bytes as a big-endian block, zero padding short blocks.
+fn read_block(bytes: &[u8]) -> u128 {
+    let mut block = [0u8; BLOCK_SIZE];
+    block[..bytes.len()].copy_from_slice(bytes);
+    u128::from_be_bytes(block)
+}
+
+/// Multiplication in GF(2^128) with GCM's bit order, using masks instead of
+/// branches so the time taken doesn't depend on the key.
+fn gf_multiply(x: u128, y: u128) -> u128 {
+    let mut product = 0u128;
+    let mut v = y;
+    for bit in (0..128).rev() {
+        product ^= v & ((x >> bit) & 1).wrapping_neg();
+        v = (v >> 1) ^ (REDUCTION & (v & 1).wrapping_neg());
+    }
+    product
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, MAC_KEY_SIZE};
+    #[cfg(feature = "alloc")]
+    use crate::{OtpError, MAC_TAG_SIZE};
+    use std::convert::TryInto;
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn key(hex: &str) -> [u8; MAC_KEY_SIZE] {
+        bytes(hex).try_into().unwrap()
+    }
+
+    // Keys are AES-GCM's hash key and encrypted first counter block, so the
+    // tags are the GCM tags for these ciphertexts.
+    #[test]
+    fn matches_gcm_known_answers() {
+        let cases = [
+            (
+                "66e94bd4ef8a2c3b884cfa59ca342b2e58e2fccefa7e3061367f1d57a4e7455a",
+                "",
+                "58e2fccefa7e3061367f1d57a4e7455a",
+            ),
+            (
+                "66e94bd4ef8a2c3b884cfa59ca342b2e58e2fccefa7e3061367f1d57a4e7455a",
+                "0388dace60b6a392f328c2b971b2fe78",
+                "ab6e47d42cec13bdf53a67b21257bddf",
+            ),
+            (
+                "c6a13b37878f5b826f4f8162a1c8d879435b9ba12d75a4be8a977ea3cd011890",
+                "c704c2ee176e9e3720f203f859d41e28d54962c63998908695019e5b71368218edc1fb9b95facec3249b6e",
+                "87ff463fbfb59cd07583e69962712252",
+            ),
+        ];
+
+        for (key_hex, message_hex, tag_hex) in cases.iter() {
+            let tag = OneTimePad::compute_tag(&key(key_hex), &bytes(message_hex));
+
+            assert_eq!(tag.to_vec(), bytes(tag_hex));
+        }
+    }
+
+    #[test]
+    fn any_bit_flip_fails_verification() {
+        let mut mac_key = [0u8; MAC_KEY_SIZE];
+        OneTimePad::generate_random_pad_into(&mut mac_key).unwrap();
+        let mut encrypted_data = [0u8; 40];
+        OneTimePad::generate_random_pad_into(&mut encrypted_data).unwrap();
+        let tag = OneTimePad::compute_tag(&mac_key, &encrypted_data);
+        assert!(OneTimePad::verify_tag(&mac_key, &encrypted_data, &tag));
+
+        for bit in 0..encrypted_data.len() * 8 {
+            let mut tampered = encrypted_data;
+            tampered[bit / 8] ^= 1 << (bit % 8);
+
+            assert!(!OneTimePad::verify_tag(&mac_key, &tampered, &tag), "bit {}", bit);
+        }
+
+        // Appending a zero byte changes the length block.
+        let mut extended = encrypted_data.to_vec();
+        extended.push(0);
+        assert!(!OneTimePad::verify_tag(&mac_key, &extended, &tag));
+        assert!(!OneTimePad::verify_tag(&mac_key, &encrypted_data, &tag[..15]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn seal_round_trips_with_documented_layout() {
+        let plain_text = b"attack at dawn";
+        let pad = OneTimePad::generate_random_pad(MAC_KEY_SIZE + plain_text.len()).unwrap();
+
+        let sealed = OneTimePad::seal(&pad, plain_text).unwrap();
+
+        let (mac_key, xor_pad) = pad.split_at(MAC_KEY_SIZE);
+        let encrypted_data = OneTimePad::encrypt(xor_pad, plain_text).unwrap();
+        let tag = OneTimePad::compute_tag(mac_key.try_into().unwrap(), &encrypted_data);
+        assert_eq!(sealed, [&encrypted_data[..], &tag[..]].concat());
+        assert_eq!(OneTimePad::open(&pad, &sealed).unwrap(), plain_text);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn open_rejects_tampered_and_truncated_input() {
+        let plain_text = b"attack at dawn";
+        let pad = OneTimePad::generate_random_pad(MAC_KEY_SIZE + plain_text.len()).unwrap();
+        let sealed = OneTimePad::seal(&pad, plain_text).unwrap();
+
+        let mut tampered_ciphertext = sealed.clone();
+        tampered_ciphertext[3] ^= 0x20;
+        let result = OneTimePad::open(&pad, &tampered_ciphertext);
+        assert!(matches!(result, Err(OtpError::TamperDetected)));
+
+        let mut tampered_tag = sealed.clone();
+        tampered_tag[sealed.len() - 1] ^= 0x01;
+        let result = OneTimePad::open(&pad, &tampered_tag);
+        assert!(matches!(result, Err(OtpError::TamperDetected)));
+
+        let result = OneTimePad::open(&pad, &sealed[..sealed.len() - 1]);
+        assert!(matches!(result, Err(OtpError::TamperDetected)));
+        let mut extended = sealed.clone();
+        extended.push(0);
+        let result = OneTimePad::open(&pad, &extended);
+        assert!(matches!(result, Err(OtpError::TamperDetected)));
+        let result = OneTimePad::open(&pad, &sealed[..MAC_TAG_SIZE - 1]);
+        assert!(matches!(result, Err(OtpError::TamperDetected)));
+
+        // The pad must cover the MAC key as well as the message.
+        let result = OneTimePad::seal(&pad[1..], plain_text);
+        assert!(matches!(result, Err(OtpError::LengthMismatch { .. })));
+    }
+}
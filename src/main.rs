@@ -0,0 +1,115 @@
+use clap::{Parser, Subcommand};
+use one_time_pad::OneTimePad;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::process;
+
+/// Encrypt and decrypt files with a one-time pad.
+///
+/// Never encrypt two files with the same pad.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write LENGTH random bytes to a new pad file
+    Genpad {
+        /// Pad length in bytes, or with a K, M or G suffix for KiB, MiB or GiB
+        #[arg(long, value_parser = parse_length)]
+        length: u64,
+        #[arg(long)]
+        out: PathBuf,
+        /// Replace OUT if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt a file with a pad file at least as long as it
+    Encrypt {
+        #[arg(long)]
+        pad: PathBuf,
+        #[arg(long = "in")]
+        input: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+        /// Replace OUT if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Decrypt a file with the pad file it was encrypted with
+    Decrypt {
+        #[arg(long)]
+        pad: PathBuf,
+        #[arg(long = "in")]
+        input: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+        /// Replace OUT if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+fn parse_length(length: &str) -> Result<u64, String> {
+    let (digits, multiplier) = match length.char_indices().last() {
+        Some((index, 'k')) | Some((index, 'K')) => (&length[..index], 1 << 10),
+        Some((index, 'm')) | Some((index, 'M')) => (&length[..index], 1 << 20),
+        Some((index, 'g')) | Some((index, 'G')) => (&length[..index], 1 << 30),
+        _ => (length, 1),
+    };
+    let count = digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|count| count.checked_mul(multiplier))
+        .ok_or_else(|| format!("{:?} is not a length such as 4096, 64K or 1M", length))?;
+    if count == 0 {
+        return Err("length must be greater than zero".to_string());
+    }
+    Ok(count)
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Genpad { length, out, force } => {
+            let file = OpenOptions::new()
+                .write(true)
+                .create_new(!force)
+                .create(force)
+                .truncate(force)
+                .open(&out)?;
+            let written = OneTimePad::generate_random_pad_to_writer(length, BufWriter::new(file))?;
+            eprintln!("wrote {} pad bytes to {}", written, out.display());
+        }
+        Command::Encrypt {
+            pad,
+            input,
+            out,
+            force,
+        } => {
+            let written = OneTimePad::encrypt_file(&input, &pad, &out, force)?;
+            eprintln!("encrypted {} bytes to {}", written, out.display());
+        }
+        Command::Decrypt {
+            pad,
+            input,
+            out,
+            force,
+        } => {
+            let written = OneTimePad::decrypt_file(&input, &pad, &out, force)?;
+            eprintln!("decrypted {} bytes to {}", written, out.display());
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run(Cli::parse().command) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+use std::fs::File;
+use std::ops::Deref;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::state::claim_region;
+use crate::{operate, OtpError};
+
+/// A pad file mapped into memory rather than read into a `Vec`, so pads
+/// larger than the available RAM can be used and only the pages touched are
+/// loaded.
+///
+/// Like `Pad` it dereferences to `[u8]`, so it can be passed to `encrypt` or
+/// `encrypt_with_offset` directly, and like `PadState` it can hand out
+/// successive unused regions.
+///
+/// The file must not be truncated while it is mapped. `next_region` and
+/// `region_at` check the file's length first and fail with
+/// `PadFileTruncated` if it has shrunk, but bytes that were already handed
+/// out, or reached through slicing, are not protected: reading them after a
+/// truncation crashes the process (`SIGBUS` on Unix).
+///
+/// ```rust,no_run
+/// use one_time_pad::{MappedPad, OneTimePad};
+/// use std::error::Error;
+/// use std::path::Path;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let mut pad = MappedPad::open(Path::new("pad.bin"))?;
+///     let encrypted_data = pad.encrypt(&[1, 2, 3, 4, 5, 6])?;
+///     let same = OneTimePad::encrypt(&pad[..6], &[1, 2, 3, 4, 5, 6])?;
+///     assert_eq!(encrypted_data, same);
+///     Ok(())
+/// }
+/// ```
+pub struct MappedPad {
+    map: Mmap,
+    file: File,
+    consumed: usize,
+}
+
+impl MappedPad {
+    /// Maps the pad file at `path`. Fails with `ZeroLength` for an empty
+    /// file.
+    pub fn open(path: &Path) -> Result<Self, OtpError> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() == 0 {
+            return Err(OtpError::ZeroLength);
+        }
+
+        // Safety: the mapping is read-only, and the type documents that the
+        // file must not be truncated while mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(MappedPad { map, file, consumed: 0 })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Number of pad bytes that have been handed out.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Number of pad bytes that are still unused.
+    pub fn remaining(&self) -> usize {
+        self.map.len() - self.consumed
+    }
+
+    /// Hands out the next `length` unused pad bytes and marks them as used.
+    pub fn next_region(&mut self, length: usize) -> Result<&[u8], OtpError> {
+        let offset = self.consumed;
+        self.region_at(offset, length)
+    }
+
+    /// Hands out `length` pad bytes starting at `offset`, like
+    /// `PadState::region_at`. Fails with `PadFileTruncated` if the file is
+    /// now shorter than when it was mapped.
+    pub fn region_at(&mut self, offset: usize, length: usize) -> Result<&[u8], OtpError> {
+        let end = claim_region(self.map.len(), self.consumed, offset, length)?;
+
+        let expected = self.map.len() as u64;
+        let found = self.file.metadata()?.len();
+        if found < expected {
+            return Err(OtpError::PadFileTruncated { expected, found });
+        }
+
+        self.consumed = end;
+        Ok(&self.map[offset..end])
+    }
+
+    /// Encrypts with the next unused region of the pad.
+    pub fn encrypt(&mut self, plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        self.operate(plain_text_buffer)
+    }
+
+    /// Decrypts with the next unused region of the pad.
+    pub fn decrypt(&mut self, encrypted_data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        self.operate(encrypted_data_buffer)
+    }
+
+    fn operate(&mut self, data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        if data_buffer.is_empty() {
+            return Err(OtpError::EmptyBuffer);
+        }
+        let region = self.next_region(data_buffer.len())?;
+        operate(region, data_buffer)
+    }
+}
+
+impl Deref for MappedPad {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl AsRef<[u8]> for MappedPad {
+    fn as_ref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl fmt::Debug for MappedPad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedPad")
+            .field("len", &self.map.len())
+            .field("consumed", &self.consumed)
+            .finish()
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{OneTimePad, OtpError};
+
+/// Ciphertext together with an optional identifier for the pad it was
+/// encrypted with, so stored messages can be matched back to their pads.
+///
+/// With the `serde` feature this can be serialized to JSON, bincode or any
+/// other serde format. The ciphertext is written as a byte string, so binary
+/// formats store it compactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EncryptedMessage {
+    pub pad_id: Option<String>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::bytes"))]
+    pub ciphertext: Vec<u8>,
+}
+
+impl OneTimePad {
+    /// Encrypts `plain_text_buffer` and labels the result with `pad_id`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let message = OneTimePad::encrypt_message(Some("pad-0001".to_string()), &pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Message: {:?}", message);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_message(
+        pad_id: Option<String>,
+        pad_buffer: &[u8],
+        plain_text_buffer: &[u8],
+    ) -> Result<EncryptedMessage, OtpError> {
+        Ok(EncryptedMessage {
+            pad_id,
+            ciphertext: OneTimePad::encrypt(pad_buffer, plain_text_buffer)?,
+        })
+    }
+
+    /// Decrypts a message produced by `encrypt_message`. The pad id is only a
+    /// label; finding the matching pad is up to the caller.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let message = OneTimePad::encrypt_message(None, &pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     let decrypted_data = OneTimePad::decrypt_message(&pad, &message)?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_message(pad_buffer: &[u8], message: &EncryptedMessage) -> Result<Vec<u8>, OtpError> {
+        OneTimePad::decrypt(pad_buffer, &message.ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{EncryptedMessage, OneTimePad, OtpError};
+
+    #[test]
+    fn message_round_trip() {
+        let pad = [7, 6, 5, 4, 3, 2];
+
+        let message = OneTimePad::encrypt_message(Some("pad-0001".into()), &pad, &[1, 2, 3, 4, 5, 6]).unwrap();
+
+        assert_eq!(message.pad_id.as_deref(), Some("pad-0001"));
+        assert_eq!(message.ciphertext, vec![6, 4, 6, 0, 6, 4]);
+        assert_eq!(OneTimePad::decrypt_message(&pad, &message).unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+        let message = EncryptedMessage {
+            pad_id: None,
+            ciphertext: vec![1, 2, 3],
+        };
+        assert!(matches!(
+            OneTimePad::decrypt_message(&pad, &message),
+            Err(OtpError::LengthMismatch { .. })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn message_json_round_trip() {
+        let pad = OneTimePad::generate_random_pad(32).unwrap();
+        let message = OneTimePad::encrypt_message(Some("pad-0002".into()), &pad, &[9; 32]).unwrap();
+
+        let json = serde_json::to_string(&message).unwrap();
+        let loaded: EncryptedMessage = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, message);
+        assert_eq!(OneTimePad::decrypt_message(&pad, &loaded).unwrap(), vec![9; 32]);
+
+        let unlabelled: EncryptedMessage = serde_json::from_str(r#"{"pad_id":null,"ciphertext":[1,2]}"#).unwrap();
+        assert_eq!(unlabelled.pad_id, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn message_cbor_round_trip_is_compact() {
+        let message = EncryptedMessage {
+            pad_id: Some("pad-0003".into()),
+            ciphertext: vec![0xff; 1000],
+        };
+
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&message, &mut cbor).unwrap();
+        let loaded: EncryptedMessage = ciborium::from_reader(&cbor[..]).unwrap();
+
+        assert_eq!(loaded, message);
+        // A sequence of integers would need two bytes for each 0xff.
+        assert!(cbor.len() < 1100, "{} bytes", cbor.len());
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::file::sidecar_path;
+use crate::{hex, OneTimePad, OtpError, Pad};
+
+/// Describes a pad so it can be matched to its correspondent later. Stored
+/// as a small text sidecar next to the pad file, `pad.bin.meta` for
+/// `pad.bin`, so the pad file itself stays raw pad bytes.
+///
+/// ```rust,no_run
+/// use one_time_pad::OneTimePad;
+/// use std::error::Error;
+/// use std::path::Path;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let (pad, metadata) = OneTimePad::generate_random_pad_with_metadata(1024, "alice")?;
+///     std::fs::write("pad.bin", &pad[..])?;
+///     OneTimePad::write_pad_metadata(Path::new("pad.bin"), &metadata)?;
+///
+///     let (pad, metadata) = OneTimePad::build_pad_from_file_with_metadata(Path::new("pad.bin"), 0, 6)?;
+///     println!("{:?} labelled {:?}", pad, metadata.map(|m| m.label));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PadMetadata {
+    /// Random identifier in UUID version 4 format.
+    pub id: String,
+    /// Creation time in seconds since the Unix epoch.
+    pub created_at: u64,
+    /// Where the pad bytes came from, e.g. `getrandom` or `hwrng`.
+    pub origin: String,
+    /// Free-form description, such as the correspondent's name.
+    pub label: String,
+}
+
+impl PadMetadata {
+    /// Creates metadata with a fresh random id and the current time.
+    pub fn new(origin: &str, label: &str) -> Result<Self, OtpError> {
+        let mut id = [0u8; 16];
+        OneTimePad::generate_random_pad_into(&mut id)?;
+        // Version 4 (random) and the RFC 4122 variant.
+        id[6] = (id[6] & 0x0f) | 0x40;
+        id[8] = (id[8] & 0x3f) | 0x80;
+        let id = hex::encode(&id);
+
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+
+        Ok(PadMetadata {
+            id: format!("{}-{}-{}-{}-{}", &id[..8], &id[8..12], &id[12..16], &id[16..20], &id[20..]),
+            created_at,
+            origin: origin.to_string(),
+            label: label.to_string(),
+        })
+    }
+
+    /// Renders the sidecar text: one `key=value` line per field, with
+    /// backslashes and line breaks in values escaped.
+    pub fn to_sidecar(&self) -> String {
+        let mut sidecar = String::new();
+        let _ = writeln!(sidecar, "id={}", escape(&self.id));
+        let _ = writeln!(sidecar, "created_at={}", self.created_at);
+        let _ = writeln!(sidecar, "origin={}", escape(&self.origin));
+        let _ = writeln!(sidecar, "label={}", escape(&self.label));
+        sidecar
+    }
+
+    /// Parses sidecar text. Unknown keys are ignored so newer versions can
+    /// add fields; `label` may be left out.
+    pub fn parse_sidecar(sidecar: &str) -> Result<Self, OtpError> {
+        let (mut id, mut created_at, mut origin, mut label) = (None, None, None, None);
+
+        for (index, text) in sidecar.lines().enumerate() {
+            let line = index + 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = text.split_once('=').ok_or(OtpError::InvalidMetadata { line })?;
+            let value = unescape(value).ok_or(OtpError::InvalidMetadata { line })?;
+            match key {
+                "id" => id = Some(value),
+                "created_at" => created_at = Some(value.parse().map_err(|_| OtpError::InvalidMetadata { line })?),
+                "origin" => origin = Some(value),
+                "label" => label = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(PadMetadata {
+            id: id.ok_or(OtpError::MissingMetadata { field: "id" })?,
+            created_at: created_at.ok_or(OtpError::MissingMetadata { field: "created_at" })?,
+            origin: origin.ok_or(OtpError::MissingMetadata { field: "origin" })?,
+            label: label.unwrap_or_default(),
+        })
+    }
+}
+
+impl OneTimePad {
+    /// Generates a pad together with metadata describing it, with origin
+    /// `getrandom`.
+    pub fn generate_random_pad_with_metadata(length: usize, label: &str) -> Result<(Pad, PadMetadata), OtpError> {
+        let pad = Self::generate_random_pad(length)?;
+        Ok((pad, PadMetadata::new("getrandom", label)?))
+    }
+
+    /// Writes the metadata sidecar for the pad file at `pad_path`.
+    pub fn write_pad_metadata(pad_path: &Path, metadata: &PadMetadata) -> io::Result<()> {
+        fs::write(sidecar_path(pad_path, ".meta"), metadata.to_sidecar())
+    }
+
+    /// Reads the metadata sidecar for the pad file at `pad_path`, or `None`
+    /// if the pad has no sidecar.
+    pub fn read_pad_metadata(pad_path: &Path) -> Result<Option<PadMetadata>, OtpError> {
+        match fs::read_to_string(sidecar_path(pad_path, ".meta")) {
+            Ok(sidecar) => PadMetadata::parse_sidecar(&sidecar).map(Some),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Like `build_pad_from_file`, but also returns the pad's metadata.
+    /// Raw pads without a sidecar load with `None`.
+    pub fn build_pad_from_file_with_metadata(
+        path: &Path,
+        offset: u64,
+        length: usize,
+    ) -> Result<(Pad, Option<PadMetadata>), OtpError> {
+        let pad = Self::build_pad_from_file(path, offset, length)?;
+        Ok((pad, Self::read_pad_metadata(path)?))
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+fn unescape(value: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut characters = value.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        match characters.next()? {
+            '\\' => unescaped.push('\\'),
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OtpError, PadMetadata};
+
+    #[test]
+    fn ids_are_version_4_uuids() {
+        let first = PadMetadata::new("getrandom", "").unwrap();
+        let second = PadMetadata::new("getrandom", "").unwrap();
+
+        assert_ne!(first.id, second.id);
+        let groups: Vec<usize> = first.id.split('-').map(str::len).collect();
+        assert_eq!(groups, vec![8, 4, 4, 4, 12]);
+        assert_eq!(&first.id[14..15], "4");
+        assert!("89ab".contains(&first.id[19..20]));
+        assert!(first.created_at > 1_600_000_000);
+    }
+
+    #[test]
+    fn sidecar_round_trip_escapes_awkward_labels() {
+        let metadata = PadMetadata {
+            id: "0f8fad5b-d9cb-469f-a165-70867728950e".to_string(),
+            created_at: 1_700_000_000,
+            origin: "hwrng".to_string(),
+            label: "bob = \"B\"\nline two \\ end".to_string(),
+        };
+
+        let sidecar = metadata.to_sidecar();
+
+        assert_eq!(sidecar.lines().count(), 4);
+        assert_eq!(PadMetadata::parse_sidecar(&sidecar).unwrap(), metadata);
+    }
+
+    #[test]
+    fn parse_reports_bad_and_missing_fields() {
+        let result = PadMetadata::parse_sidecar("id=x\ncreated_at=soon\norigin=getrandom\n");
+        assert!(matches!(result, Err(OtpError::InvalidMetadata { line: 2 })));
+
+        let result = PadMetadata::parse_sidecar("id=x\nnot a field\n");
+        assert!(matches!(result, Err(OtpError::InvalidMetadata { line: 2 })));
+
+        let result = PadMetadata::parse_sidecar("id=x\ncreated_at=1\n");
+        assert!(matches!(result, Err(OtpError::MissingMetadata { field: "origin" })));
+
+        let parsed = PadMetadata::parse_sidecar("id=x\ncreated_at=1\norigin=o\nfuture=y\n").unwrap();
+        assert_eq!(parsed.label, "");
+    }
+}
@@ -0,0 +1,312 @@
+use alloc::string::String;
+#[cfg(feature = "mlock")]
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Deref;
+
+#[cfg(feature = "mlock")]
+use crate::lock;
+use crate::wipe::wipe;
+use crate::{hex, OneTimePad, OtpError};
+
+/// Pad material that is overwritten with zeros when it is dropped.
+///
+/// `Pad` dereferences to `[u8]`, so it can be passed anywhere a byte slice is
+/// accepted.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, Pad};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = Pad::from(vec![7, 6, 5, 4, 3, 2]);
+///     let encrypted_data = OneTimePad::encrypt(&pad, &[1, 2, 3, 4, 5, 6])?;
+///     println!("Encrypted Data: {:?}", encrypted_data);
+///     Ok(())
+/// }
+/// ```
+pub struct Pad {
+    bytes: Vec<u8>,
+    /// Address and length of the range locked by `generate_locked`, kept
+    /// separately because `Zeroize` empties `bytes` before it is dropped.
+    #[cfg(feature = "mlock")]
+    locked: Option<(usize, usize)>,
+}
+
+impl Pad {
+    /// Generates `length` random pad bytes, like
+    /// `OneTimePad::generate_random_pad`.
+    pub fn generate(length: usize) -> Result<Pad, OtpError> {
+        OneTimePad::generate_random_pad(length)
+    }
+
+    /// Generates `length` random pad bytes in memory that is locked so it
+    /// can't be swapped to disk (`mlock`, or `VirtualLock` on Windows) and,
+    /// on Linux, is left out of core dumps. The memory is locked before the
+    /// random bytes are written, wiped while still locked when the pad is
+    /// dropped, and only then unlocked and freed.
+    ///
+    /// Locking is often refused, e.g. by `RLIMIT_MEMLOCK` or on platforms
+    /// without it. Then the pad is still returned, unlocked, and
+    /// `is_locked` reports `false`, so the caller can decide whether that is
+    /// acceptable.
+    ///
+    /// Locks cover whole memory pages. Dropping a locked pad unlocks every
+    /// page it touched, which can also unlock part of another locked pad
+    /// sharing a page, and pages stay excluded from core dumps after the pad
+    /// is freed.
+    ///
+    /// ```rust
+    /// use one_time_pad::{OneTimePad, Pad};
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = Pad::generate_locked(6)?;
+    ///     if !pad.is_locked() {
+    ///         eprintln!("warning: pad memory could not be locked");
+    ///     }
+    ///     let encrypted_data = OneTimePad::encrypt(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "mlock")]
+    pub fn generate_locked(length: usize) -> Result<Pad, OtpError> {
+        if length == 0 {
+            return Err(OtpError::ZeroLength);
+        }
+
+        let bytes = vec![0; length];
+        let locked = if lock::lock(&bytes) {
+            Some((bytes.as_ptr() as usize, bytes.len()))
+        } else {
+            None
+        };
+        let mut pad = Pad { bytes, locked };
+        OneTimePad::generate_random_pad_into(pad.as_mut_slice())?;
+        Ok(pad)
+    }
+
+    /// Whether the pad's memory is locked. Always `false` except for pads
+    /// from `generate_locked` where locking succeeded.
+    #[cfg(feature = "mlock")]
+    pub fn is_locked(&self) -> bool {
+        self.locked.is_some()
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Encodes the pad as lowercase hex, e.g. for printing or dictating it.
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+
+    /// Parses a pad from upper or lowercase hex. Odd-length strings and
+    /// characters that are not hex digits are rejected.
+    pub fn from_hex(hex: &str) -> Result<Pad, OtpError> {
+        hex::decode(hex).map(Pad::from)
+    }
+}
+
+impl From<Vec<u8>> for Pad {
+    fn from(bytes: Vec<u8>) -> Self {
+        Pad {
+            bytes,
+            #[cfg(feature = "mlock")]
+            locked: None,
+        }
+    }
+}
+
+/// A clone is a new, unlocked allocation, even if the original is locked.
+impl Clone for Pad {
+    fn clone(&self) -> Self {
+        Pad::from(self.bytes.clone())
+    }
+}
+
+impl Deref for Pad {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl AsRef<[u8]> for Pad {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl fmt::Debug for Pad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pad").field("len", &self.bytes.len()).finish()
+    }
+}
+
+/// Written as a byte string, like `EncryptedMessage::ciphertext`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Pad {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::bytes::serialize(&self.bytes, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Pad {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::bytes::deserialize(deserializer).map(Pad::from)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Pad {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Pad {}
+
+impl Drop for Pad {
+    fn drop(&mut self) {
+        wipe(&mut self.bytes);
+        #[cfg(feature = "mlock")]
+        if let Some((address, length)) = self.locked {
+            lock::unlock(address, length);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::pad::Pad;
+
+    #[test]
+    fn debug_does_not_print_pad_bytes() {
+        let pad = Pad::from(vec![0xAB, 0xCD]);
+
+        assert_eq!(format!("{:?}", pad), "Pad { len: 2 }");
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let pad = Pad::from(vec![0xde, 0xad, 0xbe, 0xef]);
+
+        assert_eq!(pad.to_hex(), "deadbeef");
+        assert_eq!(Pad::from_hex(&pad.to_hex()).unwrap().as_slice(), pad.as_slice());
+        assert!(Pad::from_hex("dead bee").is_err());
+    }
+
+    #[test]
+    fn derefs_to_the_wrapped_bytes() {
+        let pad = Pad::from(vec![1, 2, 3]);
+
+        assert_eq!(pad.len(), 3);
+        assert_eq!(pad.as_slice(), &[1, 2, 3]);
+        assert_eq!(&pad[1..], &[2, 3]);
+    }
+
+    #[test]
+    fn generate_makes_random_pads() {
+        let first = Pad::generate(32).unwrap();
+        let second = Pad::generate(32).unwrap();
+
+        assert_eq!(first.len(), 32);
+        assert_ne!(first.as_slice(), second.as_slice());
+        assert!(Pad::generate(0).is_err());
+    }
+
+    /// Locked memory is counted per process, so tests that lock take turns.
+    #[cfg(feature = "mlock")]
+    static LOCKING: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[cfg(feature = "mlock")]
+    #[test]
+    fn locked_pads_encrypt_like_normal_pads() {
+        use crate::OneTimePad;
+        let _locking = LOCKING.lock().unwrap();
+
+        // Whether the lock is granted depends on the machine's limits, so
+        // either outcome is fine as long as the pad works.
+        let pad = Pad::generate_locked(4096).unwrap();
+        let plain_text = vec![0x5a; 4096];
+
+        assert_eq!(pad.len(), 4096);
+        assert!(pad.iter().any(|&byte| byte != 0));
+        let copy = Pad::from(pad.to_vec());
+        assert_eq!(
+            OneTimePad::encrypt(&pad, &plain_text).unwrap(),
+            OneTimePad::encrypt(&copy, &plain_text).unwrap()
+        );
+        assert!(!copy.is_locked());
+        assert!(!pad.clone().is_locked());
+        assert!(matches!(Pad::generate_locked(0), Err(crate::OtpError::ZeroLength)));
+    }
+
+    #[cfg(all(feature = "mlock", feature = "zeroize", target_os = "linux"))]
+    #[test]
+    fn zeroized_locked_pads_still_unlock_on_drop() {
+        use zeroize::Zeroize;
+
+        // Kilobytes of locked memory in this process, per /proc.
+        fn locked_kib() -> u64 {
+            let status = std::fs::read_to_string("/proc/self/status").unwrap();
+            let line = status.lines().find(|line| line.starts_with("VmLck:")).unwrap();
+            line.split_whitespace().nth(1).unwrap().parse().unwrap()
+        }
+
+        let _locking = LOCKING.lock().unwrap();
+        let before = locked_kib();
+        let mut pad = Pad::generate_locked(1 << 16).unwrap();
+        if !pad.is_locked() {
+            // RLIMIT_MEMLOCK refused the lock; there is nothing to unlock.
+            return;
+        }
+        assert!(locked_kib() > before);
+
+        pad.zeroize();
+        assert!(pad.is_empty());
+        assert!(pad.is_locked());
+        drop(pad);
+        assert_eq!(locked_kib(), before);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips_as_bytes() {
+        let pad = Pad::from(vec![0, 1, 127, 128, 255]);
+
+        let json = serde_json::to_string(&pad).unwrap();
+        assert_eq!(json, "[0,1,127,128,255]");
+        let loaded: Pad = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.as_slice(), pad.as_slice());
+
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&pad, &mut cbor).unwrap();
+        // Major type 2 (byte string) of length 5, then the raw bytes.
+        assert_eq!(cbor, [0x45, 0, 1, 127, 128, 255]);
+        let loaded: Pad = ciborium::from_reader(&cbor[..]).unwrap();
+        assert_eq!(loaded.as_slice(), pad.as_slice());
+
+        assert!(serde_json::from_str::<Pad>("[1, 256]").is_err());
+    }
+}
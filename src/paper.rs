@@ -0,0 +1,390 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::{OtpError, Pad};
+
+const GROUP_SIZE: usize = 5;
+
+/// Layout for printing pads on paper in the traditional groups of five
+/// symbols, with numbered lines.
+///
+/// Every byte is written as two symbols, one per nibble, taken from a set of
+/// 16 distinct symbols (uppercase hex by default).
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PaperFormat};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = OneTimePad::generate_random_pad(40).unwrap();
+///     let format = PaperFormat::new().groups_per_line(4);
+///     let printout = format.format(&pad);
+///     println!("{}", printout);
+///     assert_eq!(format.parse(&printout)?.as_slice(), pad.as_slice());
+///     Ok(())
+/// }
+/// ```
+///
+/// A missing final line cannot be detected when the line before it is full,
+/// so the pad length should be checked separately.
+#[derive(Debug, Clone)]
+pub struct PaperFormat {
+    groups_per_line: usize,
+    symbols: [char; 16],
+}
+
+/// Problems found while parsing a printed pad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaperError {
+    /// The symbol set contains the same symbol twice.
+    DuplicateSymbol { symbol: char },
+    /// The symbol set contains a whitespace character, which can't be told
+    /// apart from the gaps between groups.
+    WhitespaceSymbol { symbol: char },
+    /// Line `line` does not start with a line number.
+    MissingLineNumber { line: usize },
+    /// Line `line` is numbered `found`, so a line is missing or out of order.
+    LineNumber { line: usize, found: usize },
+    /// Line `line` has the wrong number of groups.
+    GroupCount {
+        line: usize,
+        found: usize,
+        expected: usize,
+    },
+    /// Group `group` of line `line` does not have five symbols.
+    GroupLength {
+        line: usize,
+        group: usize,
+        found: usize,
+    },
+    /// Line `line` contains a symbol outside the symbol set.
+    InvalidSymbol { line: usize, symbol: char },
+    /// The pad ends half way through a byte.
+    IncompleteByte,
+}
+
+impl fmt::Display for PaperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaperError::DuplicateSymbol { symbol } => {
+                write!(
+                    f,
+                    "symbol {:?} appears more than once in the symbol set",
+                    symbol
+                )
+            }
+            PaperError::WhitespaceSymbol { symbol } => {
+                write!(f, "symbol {:?} is whitespace", symbol)
+            }
+            PaperError::MissingLineNumber { line } => write!(f, "line {} has no line number", line),
+            PaperError::LineNumber { line, found } => {
+                write!(
+                    f,
+                    "line {} is numbered {}, a line is missing or out of order",
+                    line, found
+                )
+            }
+            PaperError::GroupCount {
+                line,
+                found,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "line {} has {} groups, expected {}",
+                    line, found, expected
+                )
+            }
+            PaperError::GroupLength { line, group, found } => write!(
+                f,
+                "group {} on line {} has {} symbols, expected {}",
+                group, line, found, GROUP_SIZE
+            ),
+            PaperError::InvalidSymbol { line, symbol } => {
+                write!(f, "line {} contains unknown symbol {:?}", line, symbol)
+            }
+            PaperError::IncompleteByte => write!(f, "pad ends half way through a byte"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PaperError {}
+
+impl Default for PaperFormat {
+    fn default() -> Self {
+        PaperFormat {
+            groups_per_line: 8,
+            symbols: [
+                '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F',
+            ],
+        }
+    }
+}
+
+impl PaperFormat {
+    pub fn new() -> Self {
+        PaperFormat::default()
+    }
+
+    /// Sets how many five-symbol groups are printed per line (at least one).
+    pub fn groups_per_line(mut self, groups_per_line: usize) -> Self {
+        self.groups_per_line = groups_per_line.max(1);
+        self
+    }
+
+    /// Replaces the 16 symbols used for the nibble values 0 to 15. The
+    /// symbols must all differ and none may be whitespace.
+    pub fn symbols(mut self, symbols: [char; 16]) -> Result<Self, OtpError> {
+        for (index, &symbol) in symbols.iter().enumerate() {
+            if symbol.is_whitespace() {
+                return Err(PaperError::WhitespaceSymbol { symbol }.into());
+            }
+            if symbols[..index].contains(&symbol) {
+                return Err(PaperError::DuplicateSymbol { symbol }.into());
+            }
+        }
+        self.symbols = symbols;
+        Ok(self)
+    }
+
+    /// Renders `bytes` as numbered lines of five-symbol groups.
+    pub fn format(&self, bytes: &[u8]) -> String {
+        let symbols: Vec<char> = bytes
+            .iter()
+            .flat_map(|&byte| {
+                [
+                    self.symbols[usize::from(byte >> 4)],
+                    self.symbols[usize::from(byte & 0x0f)],
+                ]
+            })
+            .collect();
+        let groups: Vec<&[char]> = symbols.chunks(GROUP_SIZE).collect();
+        let line_count = groups.len().div_ceil(self.groups_per_line);
+        let width = digits(line_count).max(3);
+
+        let mut printout = String::new();
+        for (index, line) in groups.chunks(self.groups_per_line).enumerate() {
+            let _ = write!(printout, "{:0width$} ", index + 1, width = width);
+            for group in line {
+                printout.push(' ');
+                printout.extend(group.iter());
+            }
+            printout.push('\n');
+        }
+        printout
+    }
+
+    /// Reads a printout produced by `format` back into a pad. Whitespace and
+    /// blank lines are ignored, but missing lines, groups and symbols are
+    /// reported rather than producing a short pad.
+    pub fn parse(&self, printout: &str) -> Result<Pad, OtpError> {
+        let lines: Vec<&str> = printout
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let mut nibbles = Vec::new();
+
+        for (index, text) in lines.iter().enumerate() {
+            let line = index + 1;
+            let last_line = line == lines.len();
+            let mut tokens = text.split_whitespace();
+
+            let found = tokens
+                .next()
+                .and_then(|token| token.parse::<usize>().ok())
+                .ok_or(PaperError::MissingLineNumber { line })?;
+            if found != line {
+                return Err(PaperError::LineNumber { line, found }.into());
+            }
+
+            let groups: Vec<&str> = tokens.collect();
+            if groups.is_empty()
+                || groups.len() > self.groups_per_line
+                || (!last_line && groups.len() != self.groups_per_line)
+            {
+                return Err(PaperError::GroupCount {
+                    line,
+                    found: groups.len(),
+                    expected: self.groups_per_line,
+                }
+                .into());
+            }
+
+            for (group_index, group) in groups.iter().enumerate() {
+                let last_group = last_line && group_index + 1 == groups.len();
+                let found = group.chars().count();
+                if found > GROUP_SIZE || (!last_group && found != GROUP_SIZE) {
+                    return Err(PaperError::GroupLength {
+                        line,
+                        group: group_index + 1,
+                        found,
+                    }
+                    .into());
+                }
+
+                for symbol in group.chars() {
+                    let nibble = self
+                        .symbols
+                        .iter()
+                        .position(|&candidate| candidate == symbol)
+                        .ok_or(PaperError::InvalidSymbol { line, symbol })?;
+                    nibbles.push(nibble as u8);
+                }
+            }
+        }
+
+        if nibbles.len() % 2 != 0 {
+            return Err(PaperError::IncompleteByte.into());
+        }
+
+        Ok(Pad::from(
+            nibbles
+                .chunks(2)
+                .map(|pair| pair[0] << 4 | pair[1])
+                .collect::<Vec<u8>>(),
+        ))
+    }
+}
+
+fn digits(mut value: usize) -> usize {
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, OtpError, PaperError, PaperFormat};
+
+    fn paper_error(result: Result<crate::Pad, OtpError>) -> PaperError {
+        match result {
+            Err(OtpError::Paper(e)) => e,
+            other => panic!("expected a paper error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn known_layout() {
+        let format = PaperFormat::new().groups_per_line(2);
+
+        let printout = format.format(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd]);
+
+        assert_eq!(printout, "001  01234 56789\n002  ABCD\n");
+    }
+
+    #[test]
+    fn round_trips_many_lengths() {
+        let format = PaperFormat::new();
+
+        assert!(format.parse(&format.format(&[])).unwrap().is_empty());
+        for length in 1..100 {
+            let pad = OneTimePad::generate_random_pad(length).unwrap();
+
+            let parsed = format.parse(&format.format(&pad)).unwrap();
+
+            assert_eq!(parsed.as_slice(), pad.as_slice());
+        }
+    }
+
+    #[test]
+    fn round_trips_custom_symbols() {
+        let letters = [
+            'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+        ];
+        let format = PaperFormat::new().symbols(letters).unwrap();
+        let pad = OneTimePad::generate_random_pad(200).unwrap();
+
+        let printout = format.format(&pad);
+
+        assert!(printout
+            .lines()
+            .all(|line| line[3..].chars().all(|c| c == ' ' || letters.contains(&c))));
+        assert_eq!(format.parse(&printout).unwrap().as_slice(), pad.as_slice());
+    }
+
+    #[test]
+    fn rejects_duplicate_symbols() {
+        let mut symbols = ['0'; 16];
+        symbols[1] = '1';
+
+        assert!(matches!(
+            PaperFormat::new().symbols(symbols),
+            Err(OtpError::Paper(PaperError::DuplicateSymbol { symbol: '0' }))
+        ));
+    }
+
+    #[test]
+    fn rejects_whitespace_symbols() {
+        let mut symbols = PaperFormat::new().symbols;
+        symbols[7] = ' ';
+
+        assert!(matches!(
+            PaperFormat::new().symbols(symbols),
+            Err(OtpError::Paper(PaperError::WhitespaceSymbol { symbol: ' ' }))
+        ));
+    }
+
+    #[test]
+    fn reports_mangled_printouts() {
+        let format = PaperFormat::new();
+        let pad = OneTimePad::generate_random_pad(100).unwrap();
+        let printout = format.format(&pad);
+        let lines: Vec<&str> = printout.lines().collect();
+
+        let missing_group = printout.replacen(&lines[1][lines[1].len() - 6..], "", 1);
+        let error = paper_error(format.parse(&missing_group));
+        assert_eq!(error.to_string(), "line 2 has 7 groups, expected 8");
+
+        let missing_line = [lines[0], lines[2], lines[3]].join("\n");
+        let error = paper_error(format.parse(&missing_line));
+        assert_eq!(error, PaperError::LineNumber { line: 2, found: 3 });
+
+        let short_group = printout.replacen(&lines[0][5..10], &lines[0][5..9], 1);
+        let error = paper_error(format.parse(&short_group));
+        assert_eq!(
+            error,
+            PaperError::GroupLength {
+                line: 1,
+                group: 1,
+                found: 4
+            }
+        );
+
+        let bad_symbol = printout.replacen(&lines[2][..6], &[&lines[2][..5], "Z"].concat(), 1);
+        let error = paper_error(format.parse(&bad_symbol));
+        assert_eq!(
+            error,
+            PaperError::InvalidSymbol {
+                line: 3,
+                symbol: 'Z'
+            }
+        );
+
+        let no_numbers: String = printout
+            .lines()
+            .map(|line| &line[5..])
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(format.parse(&no_numbers).is_err());
+    }
+
+    #[test]
+    fn ignores_extra_whitespace() {
+        let format = PaperFormat::new().groups_per_line(2);
+
+        let parsed = format
+            .parse("\n  001   01234\t56789 \n\n002 ABCD\n\n")
+            .unwrap();
+
+        assert_eq!(
+            parsed.as_slice(),
+            &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd]
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use crate::{error_check, xor, OneTimePad, OtpError};
+
+const PARALLEL_CHUNK_SIZE: usize = 64 * 1024;
+
+impl OneTimePad {
+    /// Same result as `encrypt`, but large buffers are split into chunks of at
+    /// least 64 KiB that are XORed on the rayon thread pool. Buffers too small
+    /// to split take the serial path.
+    ///
+    /// Run `cargo run --release --features parallel --example parallel_crossover`
+    /// to find where this starts to pay off on a given machine, and use
+    /// `encrypt_parallel_chunked` to tune the chunk size.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_parallel(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_parallel(pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        operate_parallel(pad_buffer, plain_text_buffer, PARALLEL_CHUNK_SIZE)
+    }
+
+    /// Like `encrypt_parallel`, but no thread is given fewer than `min_chunk`
+    /// bytes. Buffers shorter than two chunks are encrypted serially so small
+    /// messages don't pay for thread overhead. A `min_chunk` of 0 is treated
+    /// as 1.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_parallel_chunked(&pad, &[1, 2, 3, 4, 5, 6], 1024 * 1024)?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_parallel_chunked(
+        pad_buffer: &[u8],
+        plain_text_buffer: &[u8],
+        min_chunk: usize,
+    ) -> Result<Vec<u8>, OtpError> {
+        operate_parallel(pad_buffer, plain_text_buffer, min_chunk)
+    }
+
+    /// Same result as `decrypt`, computed in parallel. See `encrypt_parallel`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let decrypted_data = OneTimePad::decrypt_parallel(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_parallel(pad_buffer: &[u8], encrypted_data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        operate_parallel(pad_buffer, encrypted_data_buffer, PARALLEL_CHUNK_SIZE)
+    }
+
+    /// Same result as `decrypt`, computed in parallel. See
+    /// `encrypt_parallel_chunked`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let decrypted_data = OneTimePad::decrypt_parallel_chunked(&pad, &[1, 2, 3, 4, 5, 6], 1024 * 1024)?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_parallel_chunked(
+        pad_buffer: &[u8],
+        encrypted_data_buffer: &[u8],
+        min_chunk: usize,
+    ) -> Result<Vec<u8>, OtpError> {
+        operate_parallel(pad_buffer, encrypted_data_buffer, min_chunk)
+    }
+}
+
+fn operate_parallel(pad_buffer: &[u8], data_buffer: &[u8], min_chunk: usize) -> Result<Vec<u8>, OtpError> {
+    error_check(pad_buffer, data_buffer)?;
+
+    let mut result = data_buffer.to_vec();
+    let chunk_size = chunk_size(result.len(), min_chunk, rayon::current_num_threads());
+    if runs_serially(result.len(), chunk_size) {
+        xor(pad_buffer, &mut result);
+    } else {
+        // par_chunks leaves any remainder in a shorter final chunk, and both
+        // sides are split at the same offsets because their lengths match.
+        result
+            .par_chunks_mut(chunk_size)
+            .zip(pad_buffer.par_chunks(chunk_size))
+            .for_each(|(data, pad)| xor(pad, data));
+    }
+
+    Ok(result)
+}
+
+/// Fewer than two full chunks isn't worth handing to other threads.
+fn runs_serially(length: usize, chunk_size: usize) -> bool {
+    length / 2 < chunk_size
+}
+
+/// Spreads `length` bytes evenly over `threads`, but never below `min_chunk`.
+fn chunk_size(length: usize, min_chunk: usize, threads: usize) -> usize {
+    length.div_ceil(threads.max(1)).max(min_chunk).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{chunk_size, runs_serially, PARALLEL_CHUNK_SIZE};
+    use crate::{OneTimePad, OtpError};
+
+    #[test]
+    fn parallel_matches_serial() {
+        for &length in &[1, 1000, PARALLEL_CHUNK_SIZE, PARALLEL_CHUNK_SIZE * 3 + 17] {
+            let pad = OneTimePad::generate_random_pad(length).unwrap();
+            let plain_text = OneTimePad::generate_random_pad(length).unwrap();
+
+            let encrypted_data = OneTimePad::encrypt_parallel(&pad, &plain_text).unwrap();
+
+            assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+            assert_eq!(OneTimePad::decrypt_parallel(&pad, &encrypted_data).unwrap(), plain_text.as_slice());
+        }
+    }
+
+    #[test]
+    fn chunked_matches_serial_for_random_sizes() {
+        let mut sizes = [0u8; 40];
+        getrandom::getrandom(&mut sizes).unwrap();
+
+        for pair in sizes.chunks(2) {
+            let length = usize::from(pair[0]) * 97 + 1;
+            let min_chunk = usize::from(pair[1]);
+            let pad = OneTimePad::generate_random_pad(length).unwrap();
+            let plain_text = OneTimePad::generate_random_pad(length).unwrap();
+
+            let encrypted_data = OneTimePad::encrypt_parallel_chunked(&pad, &plain_text, min_chunk).unwrap();
+
+            assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+            assert_eq!(
+                OneTimePad::decrypt_parallel_chunked(&pad, &encrypted_data, min_chunk).unwrap(),
+                plain_text.as_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn buffers_under_two_chunks_run_serially() {
+        assert!(runs_serially(100, 100));
+        assert!(runs_serially(199, 100));
+        assert!(!runs_serially(200, 100));
+        assert!(runs_serially(usize::MAX, usize::MAX / 2 + 1));
+
+        // 1.5 chunks: previously split across two threads.
+        let length = chunk_size(150, 100, 8);
+        assert_eq!(length, 100);
+        assert!(runs_serially(150, length));
+    }
+
+    #[test]
+    fn chunk_size_covers_uneven_lengths() {
+        assert_eq!(chunk_size(10, 1, 3), 4);
+        assert_eq!(chunk_size(10, 5, 3), 5);
+        assert_eq!(chunk_size(10, 0, 0), 10);
+        assert_eq!(chunk_size(7, 100, 8), 100);
+
+        for length in 1..200 {
+            for threads in 1..9 {
+                let size = chunk_size(length, 1, threads);
+                assert!(size * threads >= length);
+                assert!(length.div_ceil(size) <= threads);
+            }
+        }
+    }
+
+    #[test]
+    fn parallel_checks_lengths() {
+        assert!(matches!(
+            OneTimePad::encrypt_parallel(&[1, 2], &[1, 2, 3]),
+            Err(OtpError::LengthMismatch { pad_len: 2, data_len: 3 })
+        ));
+        assert!(matches!(OneTimePad::encrypt_parallel(&[], &[]), Err(OtpError::EmptyBuffer)));
+    }
+}
@@ -0,0 +1,168 @@
+use core::fmt;
+
+use crate::OneTimePad;
+
+/// Pads shorter than this are rejected outright.
+const MIN_PAD_LENGTH: usize = 16;
+/// The byte frequency test needs about five expected hits per byte value to
+/// mean anything, so shorter pads skip it.
+pub(crate) const CHI_SQUARE_MIN_LENGTH: usize = 256 * 5;
+/// With 255 degrees of freedom a uniformly random pad scores above this less
+/// than once in a billion tries.
+pub(crate) const CHI_SQUARE_LIMIT: f64 = 420.0;
+
+/// The sanity check a pad failed in `check_pad_quality`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PadQualityError {
+    /// The pad is shorter than `minimum` bytes.
+    TooShort { length: usize, minimum: usize },
+    /// Every byte of the pad is `byte`.
+    AllIdentical { byte: u8 },
+    /// The byte frequencies are too uneven for random data.
+    SkewedDistribution { chi_square: f64, limit: f64 },
+}
+
+impl fmt::Display for PadQualityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PadQualityError::TooShort { length, minimum } => write!(
+                f,
+                "pad is {} bytes, at least {} are needed",
+                length, minimum
+            ),
+            PadQualityError::AllIdentical { byte } => {
+                write!(f, "every pad byte is {:#04x}", byte)
+            }
+            PadQualityError::SkewedDistribution { chi_square, limit } => write!(
+                f,
+                "pad byte frequencies are not random (chi-square {:.1}, limit {:.1})",
+                chi_square, limit
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PadQualityError {}
+
+impl OneTimePad {
+    /// Cheap guardrails against using something that is obviously not random
+    /// as a pad, such as a zeroed buffer or a text file. Passing does not
+    /// prove a pad is random; it only rules out the common mistakes.
+    ///
+    /// Pads must be at least 16 bytes and not all the same byte. Pads of
+    /// 1280 bytes or more also get a chi-square test of their byte
+    /// frequencies.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(4096).unwrap();
+    ///     OneTimePad::check_pad_quality(&pad)?;
+    ///     assert!(OneTimePad::check_pad_quality(&[0; 4096]).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn check_pad_quality(pad_buffer: &[u8]) -> Result<(), PadQualityError> {
+        if pad_buffer.len() < MIN_PAD_LENGTH {
+            return Err(PadQualityError::TooShort {
+                length: pad_buffer.len(),
+                minimum: MIN_PAD_LENGTH,
+            });
+        }
+
+        if pad_buffer.iter().all(|&byte| byte == pad_buffer[0]) {
+            return Err(PadQualityError::AllIdentical { byte: pad_buffer[0] });
+        }
+
+        if pad_buffer.len() >= CHI_SQUARE_MIN_LENGTH {
+            let chi_square = chi_square(pad_buffer);
+            if chi_square > CHI_SQUARE_LIMIT {
+                return Err(PadQualityError::SkewedDistribution {
+                    chi_square,
+                    limit: CHI_SQUARE_LIMIT,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Pearson's chi-square statistic of the byte counts against a uniform
+/// distribution.
+pub(crate) fn chi_square(pad_buffer: &[u8]) -> f64 {
+    let mut counts = [0usize; 256];
+    for &byte in pad_buffer {
+        counts[usize::from(byte)] += 1;
+    }
+
+    let expected = pad_buffer.len() as f64 / 256.0;
+    counts
+        .iter()
+        .map(|&count| {
+            let difference = count as f64 - expected;
+            difference * difference / expected
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, PadQualityError};
+
+    fn random_pad(length: usize) -> Vec<u8> {
+        let mut pad = vec![0; length];
+        OneTimePad::generate_random_pad_into(&mut pad).unwrap();
+        pad
+    }
+
+    #[test]
+    fn accepts_random_pads() {
+        for &length in &[16, 100, 1280, 65536] {
+            let pad = random_pad(length);
+
+            assert_eq!(OneTimePad::check_pad_quality(&pad), Ok(()));
+        }
+    }
+
+    #[test]
+    fn rejects_short_and_constant_pads() {
+        let pad = random_pad(15);
+        assert_eq!(
+            OneTimePad::check_pad_quality(&pad),
+            Err(PadQualityError::TooShort { length: 15, minimum: 16 })
+        );
+        assert!(OneTimePad::check_pad_quality(&[]).is_err());
+
+        assert_eq!(
+            OneTimePad::check_pad_quality(&[0; 64]),
+            Err(PadQualityError::AllIdentical { byte: 0 })
+        );
+        assert_eq!(
+            OneTimePad::check_pad_quality(&[0xff; 4096]),
+            Err(PadQualityError::AllIdentical { byte: 0xff })
+        );
+    }
+
+    #[test]
+    fn rejects_structured_pads() {
+        let text = b"All work and no play makes Jack a dull boy. ".repeat(100);
+        assert!(matches!(
+            OneTimePad::check_pad_quality(&text),
+            Err(PadQualityError::SkewedDistribution { .. })
+        ));
+
+        // A counter uses every byte value equally, so the frequency test
+        // alone can't catch it; this documents the limit of the checks.
+        let counter: Vec<u8> = (0..4096u32).map(|i| i as u8).collect();
+        assert_eq!(OneTimePad::check_pad_quality(&counter), Ok(()));
+
+        let mut mostly_zero = random_pad(4096);
+        mostly_zero[..2048].iter_mut().for_each(|byte| *byte = 0);
+        assert!(OneTimePad::check_pad_quality(&mostly_zero).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use secrecy::{ExposeSecret, Secret, SecretVec};
+
+use crate::{operate, OneTimePad, OtpError};
+
+impl OneTimePad {
+    /// Generates a random pad of `length` bytes wrapped in a `SecretVec`, so
+    /// it is zeroed on drop and can only be read through `expose_secret`.
+    /// Fails with `ZeroLength` if `length` is zero.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use secrecy::ExposeSecret;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_secret_pad(6)?;
+    ///     assert_eq!(pad.expose_secret().len(), 6);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_secret_pad(length: usize) -> Result<SecretVec<u8>, OtpError> {
+        if length == 0 {
+            return Err(OtpError::ZeroLength);
+        }
+
+        let mut pad = vec![0; length];
+        Self::generate_random_pad_into(&mut pad)?;
+        Ok(Secret::new(pad))
+    }
+
+    /// Same as `encrypt`, with the pad held in a `SecretVec`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_secret_pad(6)?;
+    ///     let encrypted_data = OneTimePad::encrypt_with_secret_pad(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_with_secret_pad(
+        pad_buffer: &SecretVec<u8>,
+        plain_text_buffer: &[u8],
+    ) -> Result<Vec<u8>, OtpError> {
+        operate(pad_buffer.expose_secret(), plain_text_buffer)
+    }
+
+    /// Same as `decrypt`, with the pad held in a `SecretVec`. The plain text
+    /// is returned as a `SecretVec` too, since it is usually just as
+    /// sensitive.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use secrecy::ExposeSecret;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_secret_pad(6)?;
+    ///     let encrypted_data = OneTimePad::encrypt_with_secret_pad(&pad, &[1, 2, 3, 4, 5, 6])?;
+    ///     let decrypted_data = OneTimePad::decrypt_with_secret_pad(&pad, &encrypted_data)?;
+    ///     assert_eq!(decrypted_data.expose_secret(), &vec![1, 2, 3, 4, 5, 6]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_with_secret_pad(
+        pad_buffer: &SecretVec<u8>,
+        encrypted_data_buffer: &[u8],
+    ) -> Result<SecretVec<u8>, OtpError> {
+        operate(pad_buffer.expose_secret(), encrypted_data_buffer).map(Secret::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use secrecy::{ExposeSecret, Secret};
+
+    use crate::{OneTimePad, OtpError};
+
+    #[test]
+    fn secret_pad_gives_same_ciphertext_as_plain_pad() {
+        let pad = OneTimePad::generate_random_pad(64).unwrap();
+        let secret_pad = Secret::new(pad.to_vec());
+        let plain_text: Vec<u8> = (0..64).collect();
+
+        let encrypted_data = OneTimePad::encrypt_with_secret_pad(&secret_pad, &plain_text).unwrap();
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+
+        let decrypted_data = OneTimePad::decrypt_with_secret_pad(&secret_pad, &encrypted_data).unwrap();
+        assert_eq!(decrypted_data.expose_secret(), &plain_text);
+        assert_eq!(
+            decrypted_data.expose_secret(),
+            &OneTimePad::decrypt(&pad, &encrypted_data).unwrap()
+        );
+    }
+
+    #[test]
+    fn generated_secret_pads_work_and_reject_bad_lengths() {
+        let pad = OneTimePad::generate_secret_pad(32).unwrap();
+        assert_eq!(pad.expose_secret().len(), 32);
+        assert!(matches!(OneTimePad::generate_secret_pad(0), Err(OtpError::ZeroLength)));
+        assert!(matches!(
+            OneTimePad::encrypt_with_secret_pad(&pad, &[0; 31]),
+            Err(OtpError::LengthMismatch { pad_len: 32, data_len: 31 })
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use crate::xor_scalar;
+
+const LANE_SIZE: usize = 32;
+
+/// XORs with AVX2 when the CPU supports it, 32 bytes per instruction,
+/// otherwise falls back to the scalar loop. Lengths work like `zip`.
+pub(crate) fn xor(pad_buffer: &[u8], data_buffer: &mut [u8]) {
+    if is_x86_feature_detected!("avx2") {
+        // Safe: the CPU was just checked for AVX2.
+        unsafe { xor_avx2(pad_buffer, data_buffer) }
+    } else {
+        xor_scalar(pad_buffer, data_buffer)
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn xor_avx2(pad_buffer: &[u8], data_buffer: &mut [u8]) {
+    let length = pad_buffer.len().min(data_buffer.len());
+    let lanes_end = length - length % LANE_SIZE;
+
+    let mut index = 0;
+    while index < lanes_end {
+        // Unaligned loads and stores, so neither buffer needs to be aligned.
+        let pad = _mm256_loadu_si256(pad_buffer.as_ptr().add(index) as *const __m256i);
+        let data_ptr = data_buffer.as_mut_ptr().add(index) as *mut __m256i;
+        let data = _mm256_loadu_si256(data_ptr);
+        _mm256_storeu_si256(data_ptr, _mm256_xor_si256(pad, data));
+        index += LANE_SIZE;
+    }
+
+    xor_scalar(&pad_buffer[lanes_end..length], &mut data_buffer[lanes_end..length]);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::xor_avx2;
+    use crate::xor_scalar;
+
+    #[test]
+    fn avx2_matches_scalar_for_every_alignment() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut pad = [0u8; 200];
+        let mut data = [0u8; 200];
+        getrandom::getrandom(&mut pad).unwrap();
+        getrandom::getrandom(&mut data).unwrap();
+
+        for length in 0..=130 {
+            for offset in 0..32 {
+                let pad_slice = &pad[offset..offset + length];
+                let mut expected = data[..length].to_vec();
+                xor_scalar(pad_slice, &mut expected);
+
+                let mut result = data[..length].to_vec();
+                unsafe { xor_avx2(pad_slice, &mut result) };
+
+                assert_eq!(result, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn avx2_only_touches_overlap() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut data = [0u8; 100];
+        unsafe { xor_avx2(&[0xff; 70], &mut data) };
+
+        assert_eq!(data[..70], [0xff; 70]);
+        assert_eq!(data[70..], [0; 30]);
+    }
+}
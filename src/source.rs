@@ -0,0 +1,376 @@
+#[cfg(feature = "alloc")]
+use alloc::vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, ErrorKind, Read};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::wipe::wipe;
+#[cfg(feature = "alloc")]
+use crate::{OneTimePad, OtpError, Pad};
+
+const SOURCE_BUFFER_SIZE: usize = 256;
+
+/// Somewhere pad material can come from: the operating system RNG, a
+/// hardware device, dice rolls typed in by hand, and so on.
+///
+/// ```rust
+/// use one_time_pad::{EntropySource, OneTimePad, SourceError};
+/// use std::error::Error;
+///
+/// /// Two d16 rolls (0-15) per byte.
+/// struct Dice(Vec<u8>);
+///
+/// impl EntropySource for Dice {
+///     fn fill(&mut self, buffer: &mut [u8]) -> Result<(), SourceError> {
+///         for (filled, byte) in buffer.iter_mut().enumerate() {
+///             match (self.0.pop(), self.0.pop()) {
+///                 (Some(high), Some(low)) => *byte = high << 4 | low,
+///                 _ => return Err(SourceError::Exhausted { filled, needed: buffer.len() }),
+///             }
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let mut dice = Dice(vec![3, 14, 15, 9, 2, 6]);
+///     let pad = OneTimePad::generate_pad_from_source(&mut dice, 3)?;
+///     println!("Encryption Pad: {:?}", pad.as_slice());
+///     Ok(())
+/// }
+/// ```
+pub trait EntropySource {
+    /// Fills all of `buffer` with random bytes, or fails. Implementations
+    /// must not leave part of the buffer unfilled and report success.
+    fn fill(&mut self, buffer: &mut [u8]) -> Result<(), SourceError>;
+}
+
+/// Errors reported by an `EntropySource`. `Io` only exists with the `std`
+/// feature, so matches need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SourceError {
+    /// The source ran out after `filled` of the `needed` bytes.
+    Exhausted { filled: usize, needed: usize },
+    /// The operating system random number generator failed.
+    Rng(getrandom::Error),
+    /// Reading from a device or file failed.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceError::Exhausted { filled, needed } => write!(
+                f,
+                "entropy source ran out after {} of {} bytes",
+                filled, needed
+            ),
+            SourceError::Rng(e) => write!(f, "random number generator failed: {}", e),
+            #[cfg(feature = "std")]
+            SourceError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SourceError::Rng(e) => Some(e),
+            SourceError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<getrandom::Error> for SourceError {
+    fn from(e: getrandom::Error) -> Self {
+        SourceError::Rng(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for SourceError {
+    fn from(e: io::Error) -> Self {
+        SourceError::Io(e)
+    }
+}
+
+/// The operating system random number generator, via `getrandom`. This is
+/// what `generate_random_pad` uses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GetrandomSource;
+
+impl EntropySource for GetrandomSource {
+    fn fill(&mut self, buffer: &mut [u8]) -> Result<(), SourceError> {
+        Ok(getrandom::getrandom(buffer)?)
+    }
+}
+
+/// Reads pad material from any reader, such as a hardware RNG device or a
+/// file of dice rolls. Short reads are retried until the buffer is full; if
+/// the reader ends first the fill fails with `SourceError::Exhausted`.
+///
+/// ```rust,no_run
+/// use one_time_pad::{OneTimePad, ReaderSource};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let mut hwrng = ReaderSource::open("/dev/hwrng")?;
+///     let pad = OneTimePad::generate_pad_from_source(&mut hwrng, 32)?;
+///     println!("Encryption Pad: {:?}", pad);
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ReaderSource<R> {
+    inner: R,
+}
+
+#[cfg(feature = "std")]
+impl ReaderSource<File> {
+    /// Opens a device or file path for reading.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        File::open(path).map(ReaderSource::new)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> ReaderSource<R> {
+    pub fn new(inner: R) -> Self {
+        ReaderSource { inner }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> EntropySource for ReaderSource<R> {
+    fn fill(&mut self, buffer: &mut [u8]) -> Result<(), SourceError> {
+        let mut filled = 0;
+        while filled < buffer.len() {
+            match self.inner.read(&mut buffer[filled..]) {
+                Ok(0) => {
+                    return Err(SourceError::Exhausted {
+                        filled,
+                        needed: buffer.len(),
+                    })
+                }
+                Ok(read) => filled += read,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(SourceError::Io(e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl OneTimePad {
+    /// Builds a pad of `length` bytes from any `EntropySource`. Errors from
+    /// the source are returned as `OtpError::Source`, except random number
+    /// generator failures, which are `OtpError::Rng` as for
+    /// `generate_random_pad`.
+    ///
+    /// ```rust
+    /// use one_time_pad::{GetrandomSource, OneTimePad};
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_pad_from_source(&mut GetrandomSource, 6)?;
+    ///     println!("Encryption Pad: {:?}", pad);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_pad_from_source(source: &mut dyn EntropySource, length: usize) -> Result<Pad, OtpError> {
+        let mut pad = Pad::from(vec![0; length]);
+        source.fill(pad.as_mut_slice())?;
+        Ok(pad)
+    }
+}
+
+/// An endless iterator of random pad bytes, for encrypting data of unknown
+/// length without allocating the whole pad up front.
+///
+/// Bytes are fetched from the operating system in blocks of 256 to avoid a
+/// system call per byte, and the block is wiped when the source is dropped.
+/// If the random number generator fails the iterator ends and the error is
+/// available from `error`, so check it whenever fewer bytes came out than
+/// were expected.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PadSource};
+///
+/// fn main() {
+///     let data = vec![1, 2, 3, 4, 5, 6];
+///     let pad: Vec<u8> = PadSource::new().take(data.len()).collect();
+///     let encrypted_data: Vec<u8> = OneTimePad::xor_iter(pad.iter().copied(), data).collect();
+///     println!("Encrypted Data: {:?}", encrypted_data);
+/// }
+/// ```
+pub struct PadSource {
+    buffer: [u8; SOURCE_BUFFER_SIZE],
+    position: usize,
+    error: Option<getrandom::Error>,
+}
+
+impl PadSource {
+    pub fn new() -> Self {
+        PadSource {
+            buffer: [0; SOURCE_BUFFER_SIZE],
+            position: SOURCE_BUFFER_SIZE,
+            error: None,
+        }
+    }
+
+    /// The random number generator error that ended the iterator, if any.
+    pub fn error(&self) -> Option<getrandom::Error> {
+        self.error
+    }
+}
+
+impl Default for PadSource {
+    fn default() -> Self {
+        PadSource::new()
+    }
+}
+
+impl Iterator for PadSource {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        if self.position == SOURCE_BUFFER_SIZE {
+            if let Err(e) = getrandom::getrandom(&mut self.buffer) {
+                self.error = Some(e);
+                return None;
+            }
+            self.position = 0;
+        }
+
+        let byte = self.buffer[self.position];
+        self.buffer[self.position] = 0;
+        self.position += 1;
+        Some(byte)
+    }
+}
+
+impl fmt::Debug for PadSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PadSource").field("error", &self.error).finish()
+    }
+}
+
+impl Drop for PadSource {
+    fn drop(&mut self) {
+        wipe(&mut self.buffer);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+
+    use crate::{EntropySource, GetrandomSource, OneTimePad, OtpError, PadSource, SourceError};
+
+    #[test]
+    fn yields_bytes_across_refills() {
+        let mut source = PadSource::new();
+
+        let pad: Vec<u8> = source.by_ref().take(1000).collect();
+
+        assert_eq!(pad.len(), 1000);
+        assert!(pad.iter().any(|&byte| byte != pad[0]));
+        assert!(source.error().is_none());
+        // Successive blocks are fresh randomness, not repeats.
+        assert_ne!(pad[..256], pad[256..512]);
+    }
+
+    #[test]
+    fn composes_with_xor_iter() {
+        let plain_text = b"stream of unknown length".to_vec();
+        let pad: Vec<u8> = PadSource::new().take(plain_text.len()).collect();
+
+        let encrypted_data: Vec<u8> = OneTimePad::xor_iter(pad.iter().copied(), plain_text.clone()).collect();
+
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+
+        // Used directly, the source simply runs until the data ends.
+        let direct: Vec<u8> = OneTimePad::xor_iter(PadSource::new(), plain_text.clone()).collect();
+        assert_eq!(direct.len(), plain_text.len());
+    }
+
+    #[test]
+    fn handed_out_bytes_are_cleared_from_the_buffer() {
+        let mut source = PadSource::new();
+
+        source.by_ref().take(10).for_each(drop);
+
+        assert_eq!(source.buffer[..10], [0; 10]);
+    }
+
+    struct FailingSource;
+
+    impl EntropySource for FailingSource {
+        fn fill(&mut self, _buffer: &mut [u8]) -> Result<(), SourceError> {
+            Err(SourceError::Exhausted { filled: 0, needed: 1 })
+        }
+    }
+
+    #[test]
+    fn getrandom_source_fills_pads() {
+        let pad = OneTimePad::generate_pad_from_source(&mut GetrandomSource, 64).unwrap();
+
+        assert_eq!(pad.len(), 64);
+        assert!(pad.iter().any(|&byte| byte != pad[0]));
+    }
+
+    #[test]
+    fn source_errors_propagate() {
+        let result = OneTimePad::generate_pad_from_source(&mut FailingSource, 8);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::Source(SourceError::Exhausted { filled: 0, needed: 1 }))
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reader_source_retries_short_reads() {
+        use crate::ReaderSource;
+        use std::io::{self, Read};
+
+        struct OneByteReader<'a>(&'a [u8]);
+
+        impl Read for OneByteReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let read = buf.len().min(self.0.len()).min(1);
+                buf[..read].copy_from_slice(&self.0[..read]);
+                self.0 = &self.0[read..];
+                Ok(read)
+            }
+        }
+
+        let mut source = ReaderSource::new(OneByteReader(&[1, 2, 3, 4, 5]));
+        let pad = OneTimePad::generate_pad_from_source(&mut source, 4).unwrap();
+        assert_eq!(pad.as_slice(), &[1, 2, 3, 4]);
+
+        let result = OneTimePad::generate_pad_from_source(&mut source, 4);
+        assert!(matches!(
+            result,
+            Err(OtpError::Source(SourceError::Exhausted { filled: 1, needed: 4 }))
+        ));
+    }
+}
@@ -0,0 +1,249 @@
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{operate, OtpError, Pad};
+
+/// A pad that remembers how many of its bytes have been used and never hands
+/// out the same byte twice.
+///
+/// Both parties keep their own `PadState` over the same pad and encrypt or
+/// decrypt messages in the same order.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PadState};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = OneTimePad::generate_random_pad(12).unwrap();
+///     let mut sender = PadState::new(pad.clone());
+///     let mut receiver = PadState::new(pad);
+///
+///     let encrypted_data = sender.encrypt(&[1, 2, 3, 4, 5, 6])?;
+///     let decrypted_data = receiver.decrypt(&encrypted_data)?;
+///     println!("Decrypted Data: {:?}, {} pad bytes left", decrypted_data, sender.remaining());
+///     Ok(())
+/// }
+/// ```
+///
+/// With the `serde` feature the pad and the number of bytes consumed can be
+/// saved and restored. A saved state claiming more bytes consumed than the
+/// pad holds is rejected.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SavedPadState"))]
+pub struct PadState {
+    pad: Pad,
+    consumed: usize,
+    /// Where usage is recorded for pads opened with `PadState::open`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    journal: Option<PathBuf>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SavedPadState {
+    pad: Pad,
+    consumed: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SavedPadState> for PadState {
+    type Error = &'static str;
+
+    fn try_from(saved: SavedPadState) -> Result<Self, Self::Error> {
+        if saved.consumed > saved.pad.len() {
+            return Err("consumed is larger than the pad");
+        }
+        Ok(PadState {
+            pad: saved.pad,
+            consumed: saved.consumed,
+            #[cfg(feature = "std")]
+            journal: None,
+        })
+    }
+}
+
+impl PadState {
+    pub fn new(pad: Pad) -> Self {
+        PadState {
+            pad,
+            consumed: 0,
+            #[cfg(feature = "std")]
+            journal: None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn journaled(pad: Pad, consumed: usize, journal: PathBuf) -> Self {
+        PadState {
+            pad,
+            consumed,
+            journal: Some(journal),
+        }
+    }
+
+    /// Number of pad bytes that have been handed out.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Number of pad bytes that are still unused.
+    pub fn remaining(&self) -> usize {
+        self.pad.len() - self.consumed
+    }
+
+    /// Hands out the next `length` unused pad bytes and marks them as used.
+    pub fn next_region(&mut self, length: usize) -> Result<&[u8], OtpError> {
+        let offset = self.consumed;
+        self.region_at(offset, length)
+    }
+
+    /// Hands out `length` pad bytes starting at `offset` and marks them, and
+    /// any unused bytes before them, as used. Fails with `RegionAlreadyUsed`
+    /// if the region overlaps bytes that were handed out before.
+    ///
+    /// For a pad opened with `PadState::open` the journal is updated first,
+    /// and nothing is handed out if that fails.
+    pub fn region_at(&mut self, offset: usize, length: usize) -> Result<&[u8], OtpError> {
+        let end = claim_region(self.pad.len(), self.consumed, offset, length)?;
+
+        #[cfg(feature = "std")]
+        if let Some(journal) = &self.journal {
+            crate::journal::write_journal(journal, end)?;
+        }
+        self.consumed = end;
+        Ok(&self.pad[offset..end])
+    }
+
+    /// Encrypts with the next unused region of the pad.
+    pub fn encrypt(&mut self, plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        self.operate(plain_text_buffer)
+    }
+
+    /// Decrypts with the next unused region of the pad.
+    pub fn decrypt(&mut self, encrypted_data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        self.operate(encrypted_data_buffer)
+    }
+
+    fn operate(&mut self, data_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        if data_buffer.is_empty() {
+            return Err(OtpError::EmptyBuffer);
+        }
+        let region = self.next_region(data_buffer.len())?;
+        operate(region, data_buffer)
+    }
+}
+
+/// Checks that `length` bytes at `offset` of a pad of `pad_len` bytes are
+/// unused when the first `consumed` have been handed out, returning the end
+/// of the region.
+pub(crate) fn claim_region(pad_len: usize, consumed: usize, offset: usize, length: usize) -> Result<usize, OtpError> {
+    if offset < consumed {
+        return Err(OtpError::RegionAlreadyUsed { offset, consumed });
+    }
+
+    offset
+        .checked_add(length)
+        .filter(|&end| end <= pad_len)
+        .ok_or(OtpError::PadExhausted {
+            needed: length,
+            remaining: pad_len.saturating_sub(offset),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OtpError, Pad, PadState};
+
+    fn counting_pad(length: u8) -> Pad {
+        Pad::from((0..length).collect::<Vec<u8>>())
+    }
+
+    #[test]
+    fn sequential_messages_get_disjoint_regions() {
+        let mut state = PadState::new(counting_pad(10));
+
+        assert_eq!(state.next_region(4).unwrap(), &[0, 1, 2, 3]);
+        assert_eq!(state.next_region(4).unwrap(), &[4, 5, 6, 7]);
+        assert_eq!(state.consumed(), 8);
+        assert_eq!(state.remaining(), 2);
+    }
+
+    #[test]
+    fn reuse_of_a_region_is_rejected() {
+        let mut state = PadState::new(counting_pad(10));
+        state.next_region(4).unwrap();
+
+        let result = state.region_at(2, 2);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::RegionAlreadyUsed { offset: 2, consumed: 4 })
+        ));
+        assert_eq!(state.region_at(6, 2).unwrap(), &[6, 7]);
+        assert_eq!(state.consumed(), 8);
+    }
+
+    #[test]
+    fn running_out_of_pad_is_rejected() {
+        let mut state = PadState::new(counting_pad(10));
+        state.next_region(8).unwrap();
+
+        let result = state.encrypt(&[0, 0, 0]);
+
+        assert!(matches!(
+            result,
+            Err(OtpError::PadExhausted { needed: 3, remaining: 2 })
+        ));
+        assert_eq!(state.consumed(), 8);
+    }
+
+    #[test]
+    fn sender_and_receiver_stay_in_step() {
+        let mut sender = PadState::new(counting_pad(10));
+        let mut receiver = PadState::new(counting_pad(10));
+
+        let first = sender.encrypt(&[9, 9, 9]).unwrap();
+        let second = sender.encrypt(&[9, 9, 9]).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(receiver.decrypt(&first).unwrap(), vec![9, 9, 9]);
+        assert_eq!(receiver.decrypt(&second).unwrap(), vec![9, 9, 9]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_keeps_position() {
+        let mut state = PadState::new(counting_pad(10));
+        state.next_region(4).unwrap();
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, r#"{"pad":[0,1,2,3,4,5,6,7,8,9],"consumed":4}"#);
+        let mut loaded: PadState = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.next_region(2).unwrap(), &[4, 5]);
+
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&state, &mut cbor).unwrap();
+        let mut loaded: PadState = ciborium::from_reader(&cbor[..]).unwrap();
+        assert_eq!(loaded.consumed(), 4);
+        assert_eq!(loaded.next_region(2).unwrap(), &[4, 5]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_impossible_position() {
+        let result = serde_json::from_str::<PadState>(r#"{"pad":[0,1,2],"consumed":4}"#);
+        assert!(result.unwrap_err().to_string().contains("consumed is larger than the pad"));
+
+        let full: PadState = serde_json::from_str(r#"{"pad":[0,1,2],"consumed":3}"#).unwrap();
+        assert_eq!(full.remaining(), 0);
+    }
+}
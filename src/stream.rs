@@ -0,0 +1,388 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::io::pad_exhausted;
+use crate::{xor, OneTimePad, OtpError};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Scratch space for pad and data chunks. With the `zeroize` feature it is
+/// zeroed when dropped, including on early returns.
+#[cfg(feature = "zeroize")]
+type Buffer = zeroize::Zeroizing<Vec<u8>>;
+#[cfg(not(feature = "zeroize"))]
+type Buffer = Vec<u8>;
+
+// Only a real conversion when `Buffer` is `Zeroizing`.
+#[allow(clippy::useless_conversion)]
+pub(crate) fn buffer(length: usize) -> Buffer {
+    vec![0u8; length].into()
+}
+
+impl OneTimePad {
+    /// Encrypts `data` into `out` in fixed-size chunks, reading only as much
+    /// of `pad` as is needed. Returns the number of bytes processed and fails
+    /// with `UnexpectedEof` if the pad ends before the data does.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut encrypted_data = Vec::new();
+    ///     OneTimePad::encrypt_stream(&pad[..], &[1, 2, 3, 4, 5, 6][..], &mut encrypted_data)?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_stream<P: Read, R: Read, W: Write>(pad: P, data: R, out: W) -> io::Result<u64> {
+        operate_stream(pad, data, out, CHUNK_SIZE, |_| {})
+    }
+
+    /// Decrypts `data` into `out` in fixed-size chunks. See `encrypt_stream`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut decrypted_data = Vec::new();
+    ///     OneTimePad::decrypt_stream(&pad[..], &[1, 2, 3, 4, 5, 6][..], &mut decrypted_data)?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_stream<P: Read, R: Read, W: Write>(pad: P, data: R, out: W) -> io::Result<u64> {
+        operate_stream(pad, data, out, CHUNK_SIZE, |_| {})
+    }
+
+    /// Like `encrypt_stream`, but holds at most `chunk_size` bytes of data
+    /// and pad in memory at a time. Fails with `InvalidInput` if `chunk_size`
+    /// is zero.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut encrypted_data = Vec::new();
+    ///     OneTimePad::encrypt_stream_chunked(&pad[..], &[1, 2, 3, 4, 5, 6][..], &mut encrypted_data, 4)?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_stream_chunked<P: Read, R: Read, W: Write>(
+        pad: P,
+        data: R,
+        out: W,
+        chunk_size: usize,
+    ) -> io::Result<u64> {
+        operate_stream(pad, data, out, chunk_size, |_| {})
+    }
+
+    /// Like `decrypt_stream`, but holds at most `chunk_size` bytes of data
+    /// and pad in memory at a time. See `encrypt_stream_chunked`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut decrypted_data = Vec::new();
+    ///     OneTimePad::decrypt_stream_chunked(&pad[..], &[1, 2, 3, 4, 5, 6][..], &mut decrypted_data, 4)?;
+    ///     println!("Decrypted Data: {:?}", decrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_stream_chunked<P: Read, R: Read, W: Write>(
+        pad: P,
+        data: R,
+        out: W,
+        chunk_size: usize,
+    ) -> io::Result<u64> {
+        operate_stream(pad, data, out, chunk_size, |_| {})
+    }
+
+    /// Like `encrypt_stream`, but calls `progress` with the total number of
+    /// bytes processed so far after each chunk is written, including the
+    /// last, shorter one. The final call reports the same total that is
+    /// returned. Useful for driving a progress bar; the callback runs after
+    /// each read from `data`, at most 64 KiB at a time, not per byte. Pipes
+    /// and sockets often return less than that, so expect more calls.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut encrypted_data = Vec::new();
+    ///     OneTimePad::encrypt_stream_with_progress(&pad[..], &[1, 2, 3, 4, 5, 6][..], &mut encrypted_data, |done| {
+    ///         println!("Encrypted {} of 6 bytes", done);
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_stream_with_progress<P: Read, R: Read, W: Write, F: FnMut(u64)>(
+        pad: P,
+        data: R,
+        out: W,
+        progress: F,
+    ) -> io::Result<u64> {
+        operate_stream(pad, data, out, CHUNK_SIZE, progress)
+    }
+
+    /// Like `decrypt_stream`, but reports progress. See
+    /// `encrypt_stream_with_progress`.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mut decrypted_data = Vec::new();
+    ///     OneTimePad::decrypt_stream_with_progress(&pad[..], &[1, 2, 3, 4, 5, 6][..], &mut decrypted_data, |done| {
+    ///         println!("Decrypted {} of 6 bytes", done);
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_stream_with_progress<P: Read, R: Read, W: Write, F: FnMut(u64)>(
+        pad: P,
+        data: R,
+        out: W,
+        progress: F,
+    ) -> io::Result<u64> {
+        operate_stream(pad, data, out, CHUNK_SIZE, progress)
+    }
+
+    /// Streams `length` random pad bytes into `writer` without holding the
+    /// whole pad in memory, e.g. to create a very large pad file. Returns the
+    /// number of bytes written.
+    ///
+    /// ```rust,no_run
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    /// use std::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let file = File::create("pad.bin")?;
+    ///     let written = OneTimePad::generate_random_pad_to_writer(1 << 30, file)?;
+    ///     println!("Wrote {} pad bytes", written);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_random_pad_to_writer<W: Write>(length: u64, mut writer: W) -> Result<u64, OtpError> {
+        let mut buffer = buffer(CHUNK_SIZE);
+        let mut remaining = length;
+
+        while remaining > 0 {
+            let chunk = &mut buffer[..remaining.min(CHUNK_SIZE as u64) as usize];
+            OneTimePad::generate_random_pad_into(chunk)?;
+            writer.write_all(chunk)?;
+            #[cfg(feature = "zeroize")]
+            zeroize::Zeroize::zeroize(chunk);
+            remaining -= chunk.len() as u64;
+        }
+
+        writer.flush()?;
+        Ok(length)
+    }
+}
+
+fn operate_stream<P: Read, R: Read, W: Write, F: FnMut(u64)>(
+    mut pad: P,
+    mut data: R,
+    mut out: W,
+    chunk_size: usize,
+    mut progress: F,
+) -> io::Result<u64> {
+    if chunk_size == 0 {
+        return Err(io::Error::new(ErrorKind::InvalidInput, "chunk size cannot be zero"));
+    }
+
+    let mut pad_buffer = buffer(chunk_size);
+    let mut data_buffer = buffer(chunk_size);
+    let mut total = 0u64;
+
+    loop {
+        let read = match data.read(&mut data_buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        read_pad(&mut pad, &mut pad_buffer[..read])?;
+        xor(&pad_buffer[..read], &mut data_buffer[..read]);
+        out.write_all(&data_buffer[..read])?;
+
+        total += read as u64;
+        progress(total);
+    }
+
+    out.flush()?;
+    Ok(total)
+}
+
+fn read_pad<P: Read>(pad: &mut P, pad_buffer: &mut [u8]) -> io::Result<()> {
+    pad.read_exact(pad_buffer).map_err(|e| {
+        if e.kind() == ErrorKind::UnexpectedEof {
+            pad_exhausted()
+        } else {
+            e
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::OneTimePad;
+    use std::io::ErrorKind;
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn buffers_zeroize_on_drop() {
+        let buffer: zeroize::Zeroizing<Vec<u8>> = super::buffer(16);
+
+        assert_eq!(*buffer, vec![0; 16]);
+    }
+
+    #[test]
+    fn stream_matches_in_memory_encrypt() {
+        let plain_text: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+
+        let mut encrypted_data = Vec::new();
+        let processed = OneTimePad::encrypt_stream(&pad[..], &plain_text[..], &mut encrypted_data).unwrap();
+
+        assert_eq!(processed, plain_text.len() as u64);
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+
+        let mut decrypted_data = Vec::new();
+        OneTimePad::decrypt_stream(&pad[..], &encrypted_data[..], &mut decrypted_data).unwrap();
+
+        assert_eq!(decrypted_data, plain_text);
+    }
+
+    #[test]
+    fn stream_accepts_longer_pad() {
+        let pad = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        let mut encrypted_data = Vec::new();
+        let processed = OneTimePad::encrypt_stream(&pad[..], &[0, 0, 0][..], &mut encrypted_data).unwrap();
+
+        assert_eq!(processed, 3);
+        assert_eq!(encrypted_data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn stream_errors_when_pad_runs_out() {
+        let pad = [1, 2, 3];
+
+        let mut encrypted_data = Vec::new();
+        let result = OneTimePad::encrypt_stream(&pad[..], &[0, 0, 0, 0][..], &mut encrypted_data);
+
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn tiny_chunks_round_trip_a_large_stream() {
+        let plain_text: Vec<u8> = (0..3_000_000u32).map(|i| (i % 249) as u8).collect();
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+
+        let mut encrypted_data = Vec::new();
+        let processed =
+            OneTimePad::encrypt_stream_chunked(&pad[..], &plain_text[..], &mut encrypted_data, 7).unwrap();
+        assert_eq!(processed, plain_text.len() as u64);
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+
+        let mut decrypted_data = Vec::new();
+        OneTimePad::decrypt_stream_chunked(&pad[..], &encrypted_data[..], &mut decrypted_data, 4096).unwrap();
+        assert_eq!(decrypted_data, plain_text);
+    }
+
+    #[test]
+    fn pad_to_writer_handles_partial_final_chunk() {
+        let length = 3 * 64 * 1024 + 17;
+
+        let mut pad = Vec::new();
+        let written = OneTimePad::generate_random_pad_to_writer(length, &mut pad).unwrap();
+
+        assert_eq!(written, length);
+        assert_eq!(pad.len() as u64, length);
+        assert!(pad[pad.len() - 17..].iter().any(|&b| b != 0));
+    }
+
+    #[test]
+    fn progress_reports_every_chunk_including_the_last() {
+        let length = 2 * 64 * 1024 + 5;
+        let plain_text = vec![7u8; length];
+        let pad = OneTimePad::generate_random_pad(length).unwrap();
+
+        let mut reports = Vec::new();
+        let mut encrypted_data = Vec::new();
+        let processed =
+            OneTimePad::encrypt_stream_with_progress(&pad[..], &plain_text[..], &mut encrypted_data, |done| {
+                reports.push(done)
+            })
+            .unwrap();
+
+        assert_eq!(reports, vec![64 * 1024, 2 * 64 * 1024, length as u64]);
+        assert_eq!(processed, length as u64);
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+
+        let mut last = 0;
+        let mut decrypted_data = Vec::new();
+        OneTimePad::decrypt_stream_with_progress(&pad[..], &encrypted_data[..], &mut decrypted_data, |done| {
+            last = done
+        })
+        .unwrap();
+        assert_eq!(last, length as u64);
+        assert_eq!(decrypted_data, plain_text);
+    }
+
+    #[test]
+    fn progress_reports_every_short_read() {
+        use std::io::{self, Read};
+
+        struct ThreeByteReader<'a>(&'a [u8]);
+
+        impl Read for ThreeByteReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let read = buf.len().min(self.0.len()).min(3);
+                buf[..read].copy_from_slice(&self.0[..read]);
+                self.0 = &self.0[read..];
+                Ok(read)
+            }
+        }
+
+        let plain_text = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+
+        let mut reports = Vec::new();
+        let mut encrypted_data = Vec::new();
+        OneTimePad::encrypt_stream_with_progress(
+            &pad[..],
+            ThreeByteReader(&plain_text),
+            &mut encrypted_data,
+            |done| reports.push(done),
+        )
+        .unwrap();
+
+        assert_eq!(reports, vec![3, 6, 8]);
+        assert_eq!(encrypted_data, OneTimePad::encrypt(&pad, &plain_text).unwrap());
+    }
+
+    #[test]
+    fn zero_chunk_size_is_rejected() {
+        let result = OneTimePad::encrypt_stream_chunked(&[1][..], &[1][..], Vec::new(), 0);
+
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+}
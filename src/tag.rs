@@ -0,0 +1,171 @@
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::{OneTimePad, OtpError};
+
+const TAG_SIZE: usize = 32;
+const BLOCK_SIZE: usize = 64;
+
+impl OneTimePad {
+    /// Encrypts `plain_text_buffer` and appends an HMAC-SHA256 tag over the
+    /// ciphertext, so `decrypt_verify` can detect tampering. The result is 32
+    /// bytes longer than the plain text.
+    ///
+    /// The tag is only as strong as SHA-256, not information-theoretically
+    /// secure like the pad itself. `mac_key` should be at least 32 random
+    /// bytes kept as secret as the pad.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mac_key = OneTimePad::generate_random_pad(32).unwrap();
+    ///     let encrypted_data = OneTimePad::encrypt_with_tag(&pad, &mac_key, &[1, 2, 3, 4, 5, 6])?;
+    ///     println!("Encrypted Data: {:?}", encrypted_data);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encrypt_with_tag(
+        pad_buffer: &[u8],
+        mac_key: &[u8],
+        plain_text_buffer: &[u8],
+    ) -> Result<Vec<u8>, OtpError> {
+        let mut encrypted_data = OneTimePad::encrypt(pad_buffer, plain_text_buffer)?;
+        let tag = hmac_sha256(mac_key, &encrypted_data);
+        encrypted_data.extend_from_slice(&tag);
+        Ok(encrypted_data)
+    }
+
+    /// Checks the tag added by `encrypt_with_tag` and decrypts the ciphertext
+    /// in front of it. Fails with `TamperDetected`, without decrypting, if the
+    /// tag is missing or does not match.
+    ///
+    /// ```rust
+    /// use one_time_pad::OneTimePad;
+    /// use std::error::Error;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+    ///     let mac_key = OneTimePad::generate_random_pad(32).unwrap();
+    ///     let mut encrypted_data = OneTimePad::encrypt_with_tag(&pad, &mac_key, &[1, 2, 3, 4, 5, 6])?;
+    ///     assert_eq!(OneTimePad::decrypt_verify(&pad, &mac_key, &encrypted_data)?, vec![1, 2, 3, 4, 5, 6]);
+    ///     encrypted_data[0] ^= 1;
+    ///     assert!(OneTimePad::decrypt_verify(&pad, &mac_key, &encrypted_data).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decrypt_verify(
+        pad_buffer: &[u8],
+        mac_key: &[u8],
+        tagged_data_buffer: &[u8],
+    ) -> Result<Vec<u8>, OtpError> {
+        if tagged_data_buffer.len() < TAG_SIZE {
+            return Err(OtpError::TamperDetected);
+        }
+
+        let (encrypted_data, tag) = tagged_data_buffer.split_at(tagged_data_buffer.len() - TAG_SIZE);
+        if !OneTimePad::ct_eq(&hmac_sha256(mac_key, encrypted_data), tag) {
+            return Err(OtpError::TamperDetected);
+        }
+
+        OneTimePad::decrypt(pad_buffer, encrypted_data)
+    }
+}
+
+/// HMAC-SHA256 as defined in RFC 2104.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; TAG_SIZE] {
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let mut hashed_key = Sha256::digest(key);
+        block[..TAG_SIZE].copy_from_slice(&hashed_key);
+        crate::wipe::wipe(&mut hashed_key);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    // The padded blocks are as secret as the key itself.
+    let mut inner_block = block.map(|byte| byte ^ 0x36);
+    let mut outer_block = block.map(|byte| byte ^ 0x5c);
+
+    let mut inner = Sha256::new();
+    inner.update(&inner_block[..]);
+    inner.update(message);
+
+    let mut outer = Sha256::new();
+    outer.update(&outer_block[..]);
+    outer.update(inner.finalize());
+
+    crate::wipe::wipe(&mut block);
+    crate::wipe::wipe(&mut inner_block);
+    crate::wipe::wipe(&mut outer_block);
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::hmac_sha256;
+    use crate::{OneTimePad, OtpError};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        assert_eq!(
+            hex(&hmac_sha256(&[0x0b; 20], b"Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Keys longer than a block are hashed first.
+        assert_eq!(
+            hex(&hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn tagged_round_trip() {
+        let plain_text = b"attack at dawn";
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+        let mac_key = OneTimePad::generate_random_pad(32).unwrap();
+
+        let encrypted_data = OneTimePad::encrypt_with_tag(&pad, &mac_key, plain_text).unwrap();
+
+        assert_eq!(encrypted_data.len(), plain_text.len() + 32);
+        assert_eq!(&encrypted_data[..plain_text.len()], &OneTimePad::encrypt(&pad, plain_text).unwrap()[..]);
+        assert_eq!(OneTimePad::decrypt_verify(&pad, &mac_key, &encrypted_data).unwrap(), plain_text);
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let plain_text = b"attack at dawn";
+        let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
+        let mac_key = OneTimePad::generate_random_pad(32).unwrap();
+        let encrypted_data = OneTimePad::encrypt_with_tag(&pad, &mac_key, plain_text).unwrap();
+
+        for index in 0..encrypted_data.len() {
+            let mut tampered = encrypted_data.clone();
+            tampered[index] ^= 0x01;
+
+            let result = OneTimePad::decrypt_verify(&pad, &mac_key, &tampered);
+            assert!(matches!(result, Err(OtpError::TamperDetected)), "byte {}", index);
+        }
+
+        let wrong_key = OneTimePad::generate_random_pad(32).unwrap();
+        let result = OneTimePad::decrypt_verify(&pad, &wrong_key, &encrypted_data);
+        assert!(matches!(result, Err(OtpError::TamperDetected)));
+
+        let result = OneTimePad::decrypt_verify(&pad, &mac_key, &encrypted_data[plain_text.len() + 1..]);
+        assert!(matches!(result, Err(OtpError::TamperDetected)));
+    }
+}
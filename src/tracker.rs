@@ -0,0 +1,97 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::{OneTimePad, OtpError};
+
+/// Remembers the SHA-256 digest of every pad used through it and refuses to
+/// encrypt with the same pad twice.
+///
+/// Only exact reuse of a whole pad is detected; overlapping or partial reuse
+/// of pad material is not.
+///
+/// ```rust
+/// use one_time_pad::{OneTimePad, PadTracker};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let pad = OneTimePad::generate_random_pad(6).unwrap();
+///     let mut tracker = PadTracker::new();
+///     let encrypted_data = tracker.encrypt_tracked(&pad, &[1, 2, 3, 4, 5, 6])?;
+///     println!("Encrypted Data: {:?}", encrypted_data);
+///     assert!(tracker.encrypt_tracked(&pad, &[1, 2, 3, 4, 5, 6]).is_err());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct PadTracker {
+    used: BTreeSet<[u8; 32]>,
+}
+
+impl PadTracker {
+    pub fn new() -> Self {
+        PadTracker::default()
+    }
+
+    /// Encrypts like `OneTimePad::encrypt`, failing with `PadReused` if this
+    /// tracker has already seen `pad_buffer`. The pad is only recorded once
+    /// encryption succeeds.
+    pub fn encrypt_tracked(&mut self, pad_buffer: &[u8], plain_text_buffer: &[u8]) -> Result<Vec<u8>, OtpError> {
+        let digest = fingerprint(pad_buffer);
+        if self.used.contains(&digest) {
+            return Err(OtpError::PadReused);
+        }
+
+        let encrypted_data = OneTimePad::encrypt(pad_buffer, plain_text_buffer)?;
+        self.used.insert(digest);
+
+        Ok(encrypted_data)
+    }
+
+    pub fn is_used(&self, pad_buffer: &[u8]) -> bool {
+        self.used.contains(&fingerprint(pad_buffer))
+    }
+}
+
+fn fingerprint(pad_buffer: &[u8]) -> [u8; 32] {
+    Sha256::digest(pad_buffer).into()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{OneTimePad, OtpError, PadTracker};
+
+    #[test]
+    fn rejects_second_use_of_a_pad() {
+        let pad = OneTimePad::generate_random_pad(16).unwrap();
+        let mut tracker = PadTracker::new();
+
+        tracker.encrypt_tracked(&pad, &[1; 16]).unwrap();
+        let result = tracker.encrypt_tracked(&pad, &[2; 16]);
+
+        assert!(matches!(result, Err(OtpError::PadReused)));
+        assert!(tracker.is_used(&pad));
+    }
+
+    #[test]
+    fn accepts_distinct_pads() {
+        let mut tracker = PadTracker::new();
+
+        for _ in 0..10 {
+            let pad = OneTimePad::generate_random_pad(16).unwrap();
+            tracker.encrypt_tracked(&pad, &[1; 16]).unwrap();
+        }
+    }
+
+    #[test]
+    fn failed_encryption_does_not_mark_pad_used() {
+        let pad = OneTimePad::generate_random_pad(16).unwrap();
+        let mut tracker = PadTracker::new();
+
+        assert!(tracker.encrypt_tracked(&pad, &[1; 15]).is_err());
+        assert!(!tracker.is_used(&pad));
+        tracker.encrypt_tracked(&pad, &[1; 16]).unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+//! JavaScript bindings for browsers and Node.js, built with
+//! `wasm-pack build -- --features wasm`.
+//!
+//! Errors are thrown as JavaScript `Error`s rather than panicking, since a
+//! panic aborts the whole WebAssembly instance.
+//!
+//! Byte buffers cross the boundary as `Uint8Array`s. After
+//! `wasm-pack build --target web -- --features wasm`:
+//!
+//! ```js
+//! import init, { generatePad, encrypt, decrypt } from "./pkg/one_time_pad.js";
+//!
+//! await init();
+//! const message = new TextEncoder().encode("meet at noon");
+//! const pad = generatePad(message.length);        // Uint8Array
+//! const encrypted = encrypt(pad, message);        // Uint8Array
+//! console.log(new TextDecoder().decode(decrypt(pad, encrypted)));
+//! ```
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::OneTimePad;
+
+/// Generates `length` random pad bytes using the browser's
+/// `crypto.getRandomValues` (or Node's `crypto` module).
+#[wasm_bindgen(js_name = generatePad)]
+pub fn generate_pad(length: usize) -> Result<Vec<u8>, JsError> {
+    OneTimePad::generate_random_pad(length)
+        .map(|pad| pad.as_slice().to_vec())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Encrypts `data` with `pad`; throws if the lengths differ or are zero.
+#[wasm_bindgen]
+pub fn encrypt(pad: &[u8], data: &[u8]) -> Result<Vec<u8>, JsError> {
+    OneTimePad::encrypt(pad, data).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Decrypts `data` with `pad`; throws if the lengths differ or are zero.
+#[wasm_bindgen]
+pub fn decrypt(pad: &[u8], data: &[u8]) -> Result<Vec<u8>, JsError> {
+    OneTimePad::decrypt(pad, data).map_err(|e| JsError::new(&e.to_string()))
+}
+
+// Creating a JsError needs a JavaScript host, so these only run under
+// `wasm-pack test --node -- --features wasm`.
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+
+    use crate::wasm::{decrypt, encrypt, generate_pad};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn round_trip() {
+        let plain_text = b"hello from the browser";
+        let pad = generate_pad(plain_text.len()).unwrap();
+
+        let encrypted_data = encrypt(&pad, plain_text).unwrap();
+
+        assert_ne!(encrypted_data, plain_text);
+        assert_eq!(decrypt(&pad, &encrypted_data).unwrap(), plain_text);
+    }
+
+    #[wasm_bindgen_test]
+    fn errors_instead_of_panicking() {
+        assert!(encrypt(&[1, 2], &[1, 2, 3]).is_err());
+        assert!(decrypt(&[], &[]).is_err());
+    }
+}
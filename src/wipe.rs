@@ -0,0 +1,25 @@
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering};
+
+/// Zeroes `buffer` with volatile writes so the compiler cannot elide them.
+pub(crate) fn wipe(buffer: &mut [u8]) {
+    for byte in buffer.iter_mut() {
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::wipe::wipe;
+
+    #[test]
+    fn wipe_zeroes_every_byte() {
+        let mut buffer = vec![0xAA; 33];
+
+        wipe(&mut buffer);
+
+        assert_eq!(buffer, vec![0; 33]);
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "tokio")]
+
+use one_time_pad::OneTimePad;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("one-time-pad-{}-{}", std::process::id(), name))
+}
+
+#[tokio::test]
+async fn async_file_round_trip_matches_sync() {
+    let (input, pad, encrypted, decrypted) = (
+        temp_path("async_input"),
+        temp_path("async_pad"),
+        temp_path("async_encrypted"),
+        temp_path("async_decrypted"),
+    );
+    let plain_text: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    let pad_bytes = OneTimePad::generate_random_pad(plain_text.len() + 10).unwrap();
+    fs::write(&input, &plain_text).unwrap();
+    fs::write(&pad, pad_bytes.as_slice()).unwrap();
+
+    let encrypted_len = OneTimePad::encrypt_file_async(&input, &pad, &encrypted, false).await.unwrap();
+    let decrypted_len = OneTimePad::decrypt_file_async(&encrypted, &pad, &decrypted, false).await.unwrap();
+    let encrypted_data = fs::read(&encrypted).unwrap();
+    let decrypted_data = fs::read(&decrypted).unwrap();
+    for path in [&input, &pad, &encrypted, &decrypted].iter() {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert_eq!((encrypted_len, decrypted_len), (200_000, 200_000));
+    assert_eq!(encrypted_data, OneTimePad::encrypt(&pad_bytes[..plain_text.len()], &plain_text).unwrap());
+    assert_eq!(decrypted_data, plain_text);
+}
+
+#[tokio::test]
+async fn async_file_rejects_short_pad_and_existing_output() {
+    let (input, pad, output) = (
+        temp_path("async_short_input"),
+        temp_path("async_short_pad"),
+        temp_path("async_short_output"),
+    );
+    fs::write(&input, [1, 2, 3, 4]).unwrap();
+    fs::write(&pad, [1, 2, 3]).unwrap();
+
+    let short = OneTimePad::encrypt_file_async(&input, &pad, &output, false).await.unwrap_err();
+    let output_created = output.exists();
+
+    fs::write(&pad, [1, 2, 3, 4]).unwrap();
+    fs::write(&output, b"keep me").unwrap();
+    let existing = OneTimePad::encrypt_file_async(&input, &pad, &output, false).await.unwrap_err();
+    let kept = fs::read(&output).unwrap();
+    OneTimePad::encrypt_file_async(&input, &pad, &output, true).await.unwrap();
+    let overwritten = fs::read(&output).unwrap();
+    for path in [&input, &pad, &output].iter() {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert_eq!(short.kind(), ErrorKind::InvalidInput);
+    assert!(!output_created);
+    assert_eq!(existing.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(kept, b"keep me");
+    assert_eq!(overwritten, vec![0, 0, 0, 0]);
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::process::{Command, Output};
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("one-time-pad-cli-{}-{}", std::process::id(), name));
+    path.to_str().unwrap().to_string()
+}
+
+fn otp(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_one-time-pad")).args(args).output().unwrap()
+}
+
+#[test]
+fn genpad_encrypt_decrypt_round_trip() {
+    let (pad, plain, cipher, decrypted) = (
+        temp_path("pad"),
+        temp_path("plain"),
+        temp_path("cipher"),
+        temp_path("decrypted"),
+    );
+    fs::write(&plain, "attack at dawn\n").unwrap();
+
+    let genpad = otp(&["genpad", "--length", "15", "--out", &pad]);
+    let encrypt = otp(&["encrypt", "--pad", &pad, "--in", &plain, "--out", &cipher]);
+    let decrypt = otp(&["decrypt", "--pad", &pad, "--in", &cipher, "--out", &decrypted]);
+
+    let pad_bytes = fs::read(&pad).unwrap();
+    let cipher_bytes = fs::read(&cipher).unwrap();
+    let decrypted_bytes = fs::read(&decrypted).unwrap();
+    for path in [&pad, &plain, &cipher, &decrypted] {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert!(genpad.status.success());
+    assert!(encrypt.status.success());
+    assert!(decrypt.status.success());
+    assert_eq!(pad_bytes.len(), 15);
+    assert_ne!(cipher_bytes, b"attack at dawn\n");
+    assert_eq!(decrypted_bytes, b"attack at dawn\n");
+}
+
+#[test]
+fn refuses_short_pads_and_existing_outputs() {
+    let (pad, plain, cipher) = (temp_path("short_pad"), temp_path("short_plain"), temp_path("short_cipher"));
+    fs::write(&pad, [1, 2, 3]).unwrap();
+    fs::write(&plain, [4, 5, 6, 7]).unwrap();
+
+    let short_pad = otp(&["encrypt", "--pad", &pad, "--in", &plain, "--out", &cipher]);
+    let existing = otp(&["genpad", "--length", "4", "--out", &plain]);
+    let forced = otp(&["genpad", "--length", "4", "--force", "--out", &plain]);
+    let plain_after = fs::read(&plain).unwrap();
+    fs::remove_file(&pad).unwrap();
+    fs::remove_file(&plain).unwrap();
+
+    assert!(!short_pad.status.success());
+    assert!(String::from_utf8_lossy(&short_pad.stderr).starts_with("error:"));
+    assert!(fs::metadata(&cipher).is_err());
+    assert!(!existing.status.success());
+    assert!(forced.status.success());
+    assert_eq!(plain_after.len(), 4);
+    assert_ne!(plain_after, [4, 5, 6, 7]);
+}
+
+#[test]
+fn genpad_accepts_size_suffixes() {
+    let pad = temp_path("suffix_pad");
+
+    let kibibytes = otp(&["genpad", "--length", "2K", "--out", &pad]);
+    let length = fs::metadata(&pad).unwrap().len();
+    fs::remove_file(&pad).unwrap();
+    let invalid = otp(&["genpad", "--length", "2X", "--out", &pad]);
+
+    assert!(kibibytes.status.success());
+    assert_eq!(length, 2048);
+    assert!(!invalid.status.success());
+    assert!(fs::metadata(&pad).is_err());
+}
+
+#[test]
+fn genpad_rejects_zero_length() {
+    let pad = temp_path("zero_pad");
+
+    let zero = otp(&["genpad", "--length", "0", "--out", &pad]);
+    let zero_kibibytes = otp(&["genpad", "--length", "0K", "--out", &pad]);
+
+    assert!(!zero.status.success());
+    assert!(String::from_utf8_lossy(&zero.stderr).contains("length must be greater than zero"));
+    assert!(!zero_kibibytes.status.success());
+    assert!(fs::metadata(&pad).is_err());
+}
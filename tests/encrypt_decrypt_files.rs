@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 use one_time_pad::OneTimePad;
 use std::io::Read;
 use std::{fs::File, io::Write};
@@ -12,14 +14,14 @@ fn read_example_file_encrypt_write_then_decrypt() {
     file.read_to_end(&mut plain_text).unwrap();
 
     let pad = OneTimePad::generate_random_pad(plain_text.len()).unwrap();
-    let encrypted_data = OneTimePad::encrypt(&pad, &plain_text);
-    encrypted_file.write(&encrypted_data).unwrap();
+    let encrypted_data = OneTimePad::encrypt(&pad, &plain_text).unwrap();
+    encrypted_file.write_all(&encrypted_data).unwrap();
 
     let mut encrypted_text = Vec::new();
     let mut encrypted_file = File::open("example.txt.encrypted").unwrap();
     encrypted_file.read_to_end(&mut encrypted_text).unwrap();
 
-    let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_text);
+    let decrypted_data = OneTimePad::decrypt(&pad, &encrypted_text).unwrap();
 
     assert_eq!(plain_text, decrypted_data);
 }
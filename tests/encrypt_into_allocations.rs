@@ -0,0 +1,43 @@
+use one_time_pad::OneTimePad;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    // Only the test thread is counted; the harness allocates on its own threads.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn encrypt_into_does_not_allocate() {
+    let mut pad = vec![0u8; 4096];
+    OneTimePad::generate_random_pad_into(&mut pad).unwrap();
+    let plain_text = vec![42u8; 4096];
+    let mut encrypted_data = vec![0u8; 4096];
+    let mut decrypted_data = vec![0u8; 4096];
+
+    let before = ALLOCATIONS.with(Cell::get);
+    for _ in 0..1000 {
+        OneTimePad::encrypt_into(&pad, &plain_text, &mut encrypted_data).unwrap();
+        OneTimePad::decrypt_into(&pad, &encrypted_data, &mut decrypted_data).unwrap();
+    }
+    let after = ALLOCATIONS.with(Cell::get);
+
+    assert_eq!(before, after);
+    assert_eq!(plain_text, decrypted_data);
+}
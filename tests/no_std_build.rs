@@ -0,0 +1,47 @@
+//! Builds the crate for a bare-metal ARM target to prove it stays `no_std`,
+//! both with and without `alloc`. Skipped when the target is not installed
+//! (`rustup target add thumbv7em-none-eabihf`).
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+fn target_installed() -> bool {
+    Command::new("rustc")
+        .args(["--print", "sysroot"])
+        .output()
+        .map(|output| {
+            let sysroot = String::from_utf8_lossy(&output.stdout);
+            Path::new(sysroot.trim()).join("lib/rustlib").join(TARGET).exists()
+        })
+        .unwrap_or(false)
+}
+
+#[test]
+fn builds_for_bare_metal_target() {
+    if !target_installed() {
+        eprintln!("skipping: {} target is not installed", TARGET);
+        return;
+    }
+
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    // A separate target directory avoids waiting on the lock held by the
+    // cargo process running this test.
+    let target_dir = Path::new(manifest_dir).join("target").join("no_std_check");
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    for features in ["", "alloc"] {
+        let status = Command::new(&cargo)
+            .current_dir(manifest_dir)
+            .args(["build", "--lib", "--no-default-features", "--target", TARGET])
+            .args(["--features", features])
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .status()
+            .unwrap();
+
+        assert!(status.success(), "no_std build failed with features {:?}", features);
+    }
+}
@@ -0,0 +1,360 @@
+#![cfg(feature = "std")]
+
+use one_time_pad::{ContainerHeader, OneTimePad, OtpError, PadBook, PadState, ReaderSource, SourceError};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("one-time-pad-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn build_pad_from_file_reads_requested_region() {
+    let path = temp_path("build_pad_region");
+    fs::write(&path, (0..32).collect::<Vec<u8>>()).unwrap();
+
+    let start = OneTimePad::build_pad_from_file(&path, 0, 16).unwrap();
+    let middle = OneTimePad::build_pad_from_file(&path, 10, 4).unwrap();
+    let end = OneTimePad::build_pad_from_file(&path, 28, 4).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(start.as_slice(), &(0..16).collect::<Vec<u8>>()[..]);
+    assert_eq!(middle.as_slice(), &[10, 11, 12, 13]);
+    assert_eq!(end.as_slice(), &[28, 29, 30, 31]);
+}
+
+#[test]
+fn build_pad_from_file_rejects_region_past_end_of_file() {
+    let path = temp_path("build_pad_short");
+    fs::write(&path, [1, 2, 3, 4, 5, 6]).unwrap();
+
+    let short = OneTimePad::build_pad_from_file(&path, 0, 7);
+    let overlapping_end = OneTimePad::build_pad_from_file(&path, 4, 3);
+    let past_end = OneTimePad::build_pad_from_file(&path, 10, 1);
+    fs::remove_file(&path).unwrap();
+
+    for result in [short, overlapping_end, past_end] {
+        assert!(matches!(result, Err(OtpError::Io(e)) if e.kind() == ErrorKind::UnexpectedEof));
+    }
+}
+
+#[test]
+fn encrypt_file_then_decrypt_file_larger_than_buffer() {
+    let input = temp_path("encrypt_file_input");
+    let pad = temp_path("encrypt_file_pad");
+    let encrypted = temp_path("encrypt_file_encrypted");
+    let decrypted = temp_path("encrypt_file_decrypted");
+
+    let plain_text: Vec<u8> = (0..300_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&input, &plain_text).unwrap();
+    fs::write(&pad, OneTimePad::generate_random_pad(plain_text.len() + 10).unwrap().as_slice()).unwrap();
+
+    let encrypted_len = OneTimePad::encrypt_file(&input, &pad, &encrypted, false).unwrap();
+    let decrypted_len = OneTimePad::decrypt_file(&encrypted, &pad, &decrypted, false).unwrap();
+
+    let encrypted_data = fs::read(&encrypted).unwrap();
+    let decrypted_data = fs::read(&decrypted).unwrap();
+    for path in &[&input, &pad, &encrypted, &decrypted] {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert_eq!(encrypted_len, plain_text.len() as u64);
+    assert_eq!(decrypted_len, plain_text.len() as u64);
+    assert_ne!(encrypted_data, plain_text);
+    assert_eq!(decrypted_data, plain_text);
+}
+
+#[test]
+fn encrypt_file_rejects_short_pad_file() {
+    let input = temp_path("short_pad_input");
+    let pad = temp_path("short_pad_pad");
+    let output = temp_path("short_pad_output");
+
+    fs::write(&input, [1, 2, 3, 4]).unwrap();
+    fs::write(&pad, [1, 2, 3]).unwrap();
+
+    let result = OneTimePad::encrypt_file(&input, &pad, &output, false);
+    let output_exists = output.exists();
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&pad).unwrap();
+
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert!(!output_exists);
+}
+
+#[test]
+fn encrypt_file_only_overwrites_when_asked() {
+    let input = temp_path("overwrite_input");
+    let pad = temp_path("overwrite_pad");
+    let output = temp_path("overwrite_output");
+
+    fs::write(&input, [1, 2, 3]).unwrap();
+    fs::write(&pad, [4, 5, 6]).unwrap();
+    fs::write(&output, b"existing").unwrap();
+
+    let refused = OneTimePad::encrypt_file(&input, &pad, &output, false);
+    let kept = fs::read(&output).unwrap();
+    let overwritten = OneTimePad::encrypt_file(&input, &pad, &output, true);
+    let replaced = fs::read(&output).unwrap();
+    for path in &[&input, &pad, &output] {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert_eq!(refused.unwrap_err().kind(), ErrorKind::AlreadyExists);
+    assert_eq!(kept, b"existing");
+    assert_eq!(overwritten.unwrap(), 3);
+    assert_eq!(replaced, vec![5, 7, 5]);
+}
+
+#[test]
+fn generate_random_pad_to_writer_fills_a_file() {
+    let path = temp_path("pad_to_writer");
+    let length = 3 * 1024 * 1024 + 5;
+
+    let file = fs::File::create(&path).unwrap();
+    let written = OneTimePad::generate_random_pad_to_writer(length, file).unwrap();
+    let pad = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(written, length);
+    assert_eq!(pad.len() as u64, length);
+    assert!(pad.iter().any(|&b| b != 0));
+}
+
+#[test]
+fn reader_source_reads_pad_from_device_path() {
+    let path = temp_path("reader_source");
+    fs::write(&path, (0..16).collect::<Vec<u8>>()).unwrap();
+
+    let mut source = ReaderSource::open(&path).unwrap();
+    let pad = OneTimePad::generate_pad_from_source(&mut source, 10).unwrap();
+    let rest = OneTimePad::generate_pad_from_source(&mut source, 10);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(pad.as_slice(), &(0..10).collect::<Vec<u8>>()[..]);
+    assert!(matches!(
+        rest,
+        Err(OtpError::Source(SourceError::Exhausted { filled: 6, needed: 10 }))
+    ));
+    assert!(ReaderSource::open(temp_path("reader_source_missing")).is_err());
+}
+
+#[test]
+fn pad_file_round_trip() {
+    let path = temp_path("pad_file_round_trip");
+    let pad = OneTimePad::generate_random_pad(1000).unwrap();
+
+    OneTimePad::write_pad_file(&path, &pad).unwrap();
+    let bytes = fs::read(&path).unwrap();
+    let loaded = OneTimePad::read_pad_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(&bytes[..4], b"OTP1");
+    assert_eq!(bytes.len(), 16 + 1000);
+    assert_eq!(loaded.as_slice(), pad.as_slice());
+}
+
+#[test]
+fn read_pad_file_rejects_damaged_files() {
+    let path = temp_path("pad_file_damaged");
+    OneTimePad::write_pad_file(&path, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    let good = fs::read(&path).unwrap();
+
+    let mut bad_magic = good.clone();
+    bad_magic[0] = b'X';
+    let mut flipped_bit = good.clone();
+    flipped_bit[20] ^= 1;
+    let mut wrong_length = good.clone();
+    wrong_length[4] = 9;
+    let truncated = good[..good.len() - 1].to_vec();
+    let mut trailing = good.clone();
+    trailing.push(0);
+
+    for bytes in [bad_magic, flipped_bit, wrong_length, truncated, trailing, good[..10].to_vec(), Vec::new()] {
+        fs::write(&path, &bytes).unwrap();
+        let error = OneTimePad::read_pad_file(&path).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData, "{:?}", bytes);
+    }
+    fs::remove_file(&path).unwrap();
+
+    // A raw pad without the header is not mistaken for a pad file.
+    fs::write(&path, OneTimePad::generate_random_pad(64).unwrap().as_slice()).unwrap();
+    assert!(OneTimePad::read_pad_file(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn container_round_trips_through_a_file() {
+    let path = temp_path("container_round_trip");
+    let pad = OneTimePad::generate_random_pad(64).unwrap();
+    let encrypted_data = OneTimePad::encrypt(&pad[16..48], [7; 32]).unwrap();
+    let header = ContainerHeader {
+        pad_id: "pad-0042".to_string(),
+        pad_offset: 16,
+    };
+
+    OneTimePad::write_container(fs::File::create(&path).unwrap(), &header, &encrypted_data).unwrap();
+    let (loaded_header, loaded_data) = OneTimePad::read_container(fs::File::open(&path).unwrap()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded_header, header);
+    let offset = loaded_header.pad_offset as usize;
+    let decrypted_data = OneTimePad::decrypt(&pad[offset..offset + loaded_data.len()], &loaded_data).unwrap();
+    assert_eq!(decrypted_data, vec![7; 32]);
+}
+
+#[test]
+fn pad_book_opens_a_pad_file() {
+    let path = temp_path("pad_book");
+    fs::write(&path, (0..16).collect::<Vec<u8>>()).unwrap();
+
+    let mut book = PadBook::open(&path).unwrap();
+
+    assert_eq!(book.next_segment(4).unwrap(), &[0, 1, 2, 3]);
+    assert_eq!(book.next_segment(4).unwrap(), &[4, 5, 6, 7]);
+    assert_eq!(book.remaining(), 8);
+
+    let mut reopened = PadBook::open(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(temp_path("pad_book.journal")).unwrap();
+
+    assert_eq!(reopened.next_segment(4).unwrap(), &[8, 9, 10, 11]);
+    assert!(PadBook::open(&path).is_err());
+}
+
+#[test]
+fn journaled_pad_is_not_reused_after_reopening() {
+    let path = temp_path("journaled_pad");
+    let journal = temp_path("journaled_pad.journal");
+    fs::write(&path, (0..10).collect::<Vec<u8>>()).unwrap();
+
+    let mut state = PadState::open(&path).unwrap();
+    let encrypted_data = state.encrypt(&[9, 9, 9, 9]).unwrap();
+    let recorded = fs::read_to_string(&journal).unwrap();
+    drop(state);
+
+    let mut reopened = PadState::open(&path).unwrap();
+    let reuse = reopened.region_at(2, 2).map(<[u8]>::to_vec);
+    let next = reopened.next_region(3).unwrap().to_vec();
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&journal).unwrap();
+
+    assert_eq!(recorded, "4\n");
+    assert_eq!(encrypted_data, vec![9, 8, 11, 10]);
+    assert!(matches!(reuse, Err(OtpError::RegionAlreadyUsed { offset: 2, consumed: 4 })));
+    assert_eq!(next, vec![4, 5, 6]);
+}
+
+#[test]
+fn journal_can_live_apart_from_the_pad() {
+    let path = temp_path("pad_with_remote_journal");
+    let journal = temp_path("remote_offset");
+    fs::write(&path, [0; 8]).unwrap();
+
+    let mut state = PadState::open_with_journal(&path, &journal).unwrap();
+    state.encrypt(&[1, 2, 3]).unwrap();
+    let recorded = fs::read_to_string(&journal).unwrap();
+    let beside_pad = fs::metadata(temp_path("pad_with_remote_journal.journal")).is_ok();
+    let reopened = PadState::open_with_journal(&path, &journal).unwrap();
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&journal).unwrap();
+
+    assert_eq!(recorded, "3\n");
+    assert!(!beside_pad);
+    assert_eq!(reopened.consumed(), 3);
+}
+
+#[test]
+fn fully_used_journaled_pad_will_not_open() {
+    let path = temp_path("used_up_pad");
+    let journal = temp_path("used_up_pad.journal");
+    fs::write(&path, [1, 2, 3, 4]).unwrap();
+
+    let mut state = PadState::open(&path).unwrap();
+    state.next_region(4).unwrap();
+    let used_up = PadState::open(&path);
+
+    fs::write(&journal, "5\n").unwrap();
+    let overrun = PadState::open(&path);
+    fs::write(&journal, "four\n").unwrap();
+    let garbled = PadState::open(&path);
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&journal).unwrap();
+
+    assert!(matches!(used_up, Err(OtpError::PadExhausted { remaining: 0, .. })));
+    assert!(matches!(overrun, Err(OtpError::Io(ref e)) if e.kind() == ErrorKind::InvalidData));
+    assert!(matches!(garbled, Err(OtpError::Io(ref e)) if e.kind() == ErrorKind::InvalidData));
+}
+
+#[test]
+fn pad_metadata_sidecar_round_trip() {
+    let path = temp_path("pad_metadata");
+    let (pad, metadata) = OneTimePad::generate_random_pad_with_metadata(32, "alice").unwrap();
+    fs::write(&path, pad.as_slice()).unwrap();
+    OneTimePad::write_pad_metadata(&path, &metadata).unwrap();
+
+    let (loaded, loaded_metadata) = OneTimePad::build_pad_from_file_with_metadata(&path, 8, 8).unwrap();
+    let sidecar_path = temp_path("pad_metadata.meta");
+    let sidecar = fs::read_to_string(&sidecar_path).unwrap();
+    fs::remove_file(&sidecar_path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.as_slice(), &pad[8..16]);
+    assert_eq!(loaded_metadata, Some(metadata.clone()));
+    assert_eq!(metadata.origin, "getrandom");
+    assert!(sidecar.contains("label=alice\n"));
+}
+
+#[test]
+fn raw_pad_without_sidecar_has_no_metadata() {
+    let path = temp_path("pad_without_metadata");
+    fs::write(&path, [1, 2, 3, 4, 5, 6]).unwrap();
+
+    let (pad, metadata) = OneTimePad::build_pad_from_file_with_metadata(&path, 0, 6).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(pad.as_slice(), &[1, 2, 3, 4, 5, 6]);
+    assert_eq!(metadata, None);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mapped_pad_matches_in_memory_pad() {
+    let path = temp_path("mapped_pad");
+    let pad = OneTimePad::generate_random_pad(100_000).unwrap();
+    fs::write(&path, pad.as_slice()).unwrap();
+    let plain_text: Vec<u8> = (0..60_000u32).map(|i| (i % 251) as u8).collect();
+
+    let mut mapped = one_time_pad::MappedPad::open(&path).unwrap();
+    let whole = OneTimePad::encrypt(&mapped[..60_000], &plain_text).unwrap();
+    let first = mapped.encrypt(&plain_text[..10]).unwrap();
+    let second = mapped.encrypt(&plain_text[10..]).unwrap();
+    let reuse = mapped.region_at(0, 4).map(<[u8]>::to_vec);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(mapped.len(), 100_000);
+    assert_eq!(whole, OneTimePad::encrypt(&pad[..60_000], &plain_text).unwrap());
+    assert_eq!([first, second].concat(), whole);
+    assert!(matches!(reuse, Err(OtpError::RegionAlreadyUsed { offset: 0, consumed: 60_000 })));
+    assert_eq!(mapped.remaining(), 40_000);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mapped_pad_rejects_empty_and_truncated_files() {
+    let path = temp_path("mapped_pad_truncated");
+    fs::write(&path, []).unwrap();
+    let empty = one_time_pad::MappedPad::open(&path);
+
+    fs::write(&path, [7; 64]).unwrap();
+    let mut mapped = one_time_pad::MappedPad::open(&path).unwrap();
+    assert_eq!(mapped.next_region(8).unwrap(), &[7; 8]);
+    fs::OpenOptions::new().write(true).open(&path).unwrap().set_len(16).unwrap();
+    let truncated = mapped.next_region(8).map(<[u8]>::to_vec);
+    fs::remove_file(&path).unwrap();
+
+    assert!(matches!(empty, Err(OtpError::ZeroLength)));
+    assert!(matches!(truncated, Err(OtpError::PadFileTruncated { expected: 64, found: 16 })));
+    assert_eq!(mapped.consumed(), 8);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "alloc")]
+
+use one_time_pad::{OneTimePad, Pad};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::slice;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+struct InspectingAllocator;
+
+static WATCHED: AtomicUsize = AtomicUsize::new(0);
+static WIPED: AtomicBool = AtomicBool::new(false);
+
+unsafe impl GlobalAlloc for InspectingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let watched = WATCHED.compare_exchange(ptr as usize, 0, Ordering::SeqCst, Ordering::SeqCst);
+        if watched.is_ok() {
+            let bytes = slice::from_raw_parts(ptr, layout.size());
+            WIPED.store(bytes.iter().all(|&b| b == 0), Ordering::SeqCst);
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: InspectingAllocator = InspectingAllocator;
+
+#[test]
+fn pad_memory_is_zeroed_before_it_is_freed() {
+    let pad = Pad::from(vec![0xAA; 64]);
+    WATCHED.store(pad.as_ptr() as usize, Ordering::SeqCst);
+    drop(pad);
+    assert!(WIPED.swap(false, Ordering::SeqCst));
+
+    let pad = OneTimePad::generate_random_pad(64).unwrap();
+    WATCHED.store(pad.as_ptr() as usize, Ordering::SeqCst);
+    drop(pad);
+    assert!(WIPED.swap(false, Ordering::SeqCst));
+}
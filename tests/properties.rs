@@ -0,0 +1,41 @@
+#![cfg(feature = "alloc")]
+
+use one_time_pad::{OneTimePad, OtpError};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// A pad and data of the same, non-zero length.
+fn pad_and_data() -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+    (1..4096usize).prop_flat_map(|length| (vec(any::<u8>(), length), vec(any::<u8>(), length)))
+}
+
+proptest! {
+    #[test]
+    fn decrypt_reverses_encrypt((pad, data) in pad_and_data()) {
+        let encrypted_data = OneTimePad::encrypt(&pad, &data).unwrap();
+
+        prop_assert_eq!(OneTimePad::decrypt(&pad, &encrypted_data).unwrap(), data);
+    }
+
+    #[test]
+    fn pad_and_data_are_interchangeable((pad, data) in pad_and_data()) {
+        prop_assert_eq!(
+            OneTimePad::encrypt(&pad, &data).unwrap(),
+            OneTimePad::encrypt(&data, &pad).unwrap()
+        );
+    }
+
+    #[test]
+    fn mismatched_lengths_are_errors(pad in vec(any::<u8>(), 1..512), data in vec(any::<u8>(), 1..512)) {
+        prop_assume!(pad.len() != data.len());
+
+        let result = OneTimePad::encrypt(&pad, &data);
+
+        prop_assert!(
+            matches!(result, Err(OtpError::LengthMismatch { pad_len, data_len })
+                if pad_len == pad.len() && data_len == data.len()),
+            "expected a length mismatch, got {:?}",
+            result
+        );
+    }
+}